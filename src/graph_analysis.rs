@@ -1,10 +1,17 @@
 //! Graph algorithms and metrics: shortest paths, degree distributions,
-//! densest-subgraph, and centralities (closeness & betweenness).
+//! densest-subgraph, k-cores and k-trusses, clustering, communities, and
+//! centralities (closeness, betweenness, PageRank, eigenvector, Katz).
 
-use petgraph::{Graph, Undirected, prelude::NodeIndex};
-use std::collections::{HashSet, VecDeque, HashMap};
+use petgraph::{Graph, Undirected, prelude::NodeIndex, visit::EdgeRef};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, VecDeque, HashMap};
+use std::error::Error;
+use std::fmt;
 
-/// Average shortest-path length (unweighted) by sampling up to `sample_size` seeds.
+/// Average shortest-path length (unweighted) from 5 uniformly sampled seeds.
 /// 
 /// # Inputs
 /// - `graph`: undirected graph with `usize` node payloads  
@@ -13,46 +20,354 @@ use std::collections::{HashSet, VecDeque, HashMap};
 /// - average geodesic distance over all reachable pairs from sampled seeds  
 /// 
 /// # Logic
-/// For each of up to 5 start nodes: BFS to compute distances in O(V+E), 
-/// accumulate all nonzero finite distances.
+/// Shorthand for [`estimate_average_path`] with `sample_size = 5` and the
+/// default seed; use that directly for the standard error and confidence
+/// interval, or [`path_length_stats`] for the exact all-pairs value.
 pub fn average_shortest_path(graph: &Graph<usize, (), Undirected>) -> f64 {
-    let node_count = graph.node_count();
-    let sample_size = 5.min(node_count);
+    let opts = EstimateOptions { sample_size: 5, ..EstimateOptions::default() };
+    estimate_average_path(graph, &opts).estimate
+}
 
-    let mut total_dist = 0.0;
-    let mut total_pairs = 0.0;
+/// How [`path_length_stats`] chooses its BFS sources.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum PathSampling {
+    /// BFS from every node: exact all-pairs statistics.
+    Exact,
+    /// BFS from `sample_size` distinct nodes drawn uniformly with `seed`.
+    Sampled { sample_size: usize, seed: u64 },
+}
 
-    for start in graph.node_indices().take(sample_size) {
-        let mut dist = vec![usize::MAX; node_count];
-        let mut queue = VecDeque::new();
-        dist[start.index()] = 0;
-        queue.push_back(start);
+/// All-pairs (or sampled) shortest-path statistics.
+///
+/// Distances are only taken between mutually reachable nodes, so on a
+/// disconnected graph eccentricities, diameter and radius are per component.
+/// In sampled mode `diameter` is a lower bound and `radius` an upper bound.
+#[derive(Debug, Clone, Serialize)]
+pub struct PathLengthStats {
+    /// Sampling mode that produced these numbers
+    pub sampling: PathSampling,
+    /// Number of BFS sources actually used
+    pub sources: usize,
+    /// Number of ordered (source, target) pairs with finite distance > 0
+    pub reachable_pairs: u64,
+    /// Mean distance over `reachable_pairs`
+    pub average_path_length: f64,
+    /// Largest eccentricity among the sources
+    pub diameter: usize,
+    /// Smallest eccentricity among the sources (0 only for isolated nodes)
+    pub radius: usize,
+    /// Eccentricity per source node payload
+    pub eccentricities: HashMap<usize, usize>,
+}
 
-        // BFS from `start`
-        while let Some(u) = queue.pop_front() {
-            let du = dist[u.index()];
-            for v in graph.neighbors(u) {
-                let vi = v.index();
-                if dist[vi] == usize::MAX {
-                    dist[vi] = du + 1;
-                    queue.push_back(v);
+/// Average shortest-path length, diameter, radius and eccentricities.
+///
+/// # Inputs
+/// - `graph`: undirected graph with `usize` node payloads
+/// - `sampling`: [`PathSampling::Exact`] or a seeded uniform sample of sources
+///
+/// # Logic
+/// One BFS per source in O(V+E), spread across the rayon thread pool with a
+/// reusable distance buffer per worker; per-source sums, pair counts and
+/// eccentricities are then reduced. Exact mode is O(V·(V+E)) total.
+pub fn path_length_stats(
+    graph: &Graph<usize, (), Undirected>,
+    sampling: PathSampling,
+) -> PathLengthStats {
+    let n = graph.node_count();
+    let sources: Vec<NodeIndex> = match sampling {
+        PathSampling::Exact => graph.node_indices().collect(),
+        PathSampling::Sampled { sample_size, seed } => {
+            let mut rng = StdRng::seed_from_u64(seed);
+            rand::seq::index::sample(&mut rng, n, sample_size.min(n))
+                .into_iter()
+                .map(NodeIndex::new)
+                .collect()
+        }
+    };
+
+    let per_source = source_distance_sums(graph, &sources);
+
+    let total_dist: u64 = per_source.iter().map(|r| r.sum).sum();
+    let reachable_pairs: u64 = per_source.iter().map(|r| r.pairs).sum();
+    let average_path_length = if reachable_pairs > 0 {
+        total_dist as f64 / reachable_pairs as f64
+    } else {
+        0.0
+    };
+
+    PathLengthStats {
+        sampling,
+        sources: per_source.len(),
+        reachable_pairs,
+        average_path_length,
+        diameter: per_source.iter().map(|r| r.eccentricity).max().unwrap_or(0),
+        radius: per_source.iter().map(|r| r.eccentricity).min().unwrap_or(0),
+        eccentricities: per_source
+            .iter()
+            .map(|r| (graph[r.source], r.eccentricity))
+            .collect(),
+    }
+}
+
+/// Options for [`estimate_average_path`] and [`estimate_average_path_adaptive`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct EstimateOptions {
+    /// Number of BFS sources (initial batch size in adaptive mode)
+    pub sample_size: usize,
+    /// RNG seed for source sampling and bootstrap resampling
+    pub seed: u64,
+    /// Two-sided confidence level of the bootstrap interval, e.g. 0.95
+    pub confidence: f64,
+    /// Number of bootstrap replicates
+    pub bootstrap_replicates: usize,
+}
+
+impl Default for EstimateOptions {
+    fn default() -> Self {
+        EstimateOptions {
+            sample_size: 32,
+            seed: 42,
+            confidence: 0.95,
+            bootstrap_replicates: 1000,
+        }
+    }
+}
+
+/// Sampled estimate of the average shortest-path length.
+#[derive(Debug, Clone, Serialize)]
+pub struct PathLengthEstimate {
+    /// Ratio estimate Σ distances / Σ reachable pairs over the sampled sources
+    pub estimate: f64,
+    /// Delta-method standard error, with finite-population correction
+    pub standard_error: f64,
+    /// Lower end of the percentile bootstrap interval
+    pub ci_low: f64,
+    /// Upper end of the percentile bootstrap interval
+    pub ci_high: f64,
+    /// Options used (`sample_size` is the initial batch in adaptive mode)
+    pub options: EstimateOptions,
+    /// Number of BFS sources actually used
+    pub sources: usize,
+    /// `true` once every node has been a source (the estimate is then exact)
+    pub exhausted: bool,
+}
+
+impl PathLengthEstimate {
+    /// Half-width of the bootstrap confidence interval.
+    pub fn half_width(&self) -> f64 {
+        (self.ci_high - self.ci_low) / 2.0
+    }
+}
+
+/// Estimate the average shortest-path length from uniformly sampled sources.
+///
+/// # Inputs
+/// - `graph`: undirected graph with `usize` node payloads
+/// - `opts`: sample size, seed, confidence level, bootstrap replicates
+///
+/// # Logic
+/// Draw `sample_size` distinct sources uniformly (seeded), BFS from each in
+/// parallel, and form the ratio estimator R = ΣSᵢ / ΣPᵢ of per-source
+/// distance sums Sᵢ over reachable-target counts Pᵢ. The standard error is
+/// the linearised (delta-method) SE of R with finite-population correction
+/// (1 − m/n); the interval is the percentile bootstrap over sources, with
+/// replicate deviations rescaled by √(1 − m/n) for the same correction.
+pub fn estimate_average_path(
+    graph: &Graph<usize, (), Undirected>,
+    opts: &EstimateOptions,
+) -> PathLengthEstimate {
+    let mut rng = StdRng::seed_from_u64(opts.seed);
+    let order = shuffled_nodes(graph, &mut rng);
+    let m = opts.sample_size.min(order.len());
+    let per_source = source_distance_sums(graph, &order[..m]);
+    summarize_estimate(&per_source, graph.node_count(), opts, &mut rng)
+}
+
+/// Keep adding sampled sources until the bootstrap CI half-width is at most
+/// `tolerance` (or every node has been used).
+///
+/// # Logic
+/// Start from `opts.sample_size` sources of one seeded random permutation;
+/// after each round, recompute the estimate and interval and, if too wide,
+/// double the number of sources. Sources are never repeated, so the result
+/// becomes exact once the permutation is exhausted.
+pub fn estimate_average_path_adaptive(
+    graph: &Graph<usize, (), Undirected>,
+    tolerance: f64,
+    opts: &EstimateOptions,
+) -> PathLengthEstimate {
+    let mut rng = StdRng::seed_from_u64(opts.seed);
+    let order = shuffled_nodes(graph, &mut rng);
+    let n = order.len();
+
+    let mut used = opts.sample_size.clamp(2.min(n), n);
+    let mut per_source = source_distance_sums(graph, &order[..used]);
+    loop {
+        let est = summarize_estimate(&per_source, n, opts, &mut rng);
+        if est.half_width() <= tolerance || used == n {
+            return est;
+        }
+        let next = (used * 2).min(n);
+        per_source.extend(source_distance_sums(graph, &order[used..next]));
+        used = next;
+    }
+}
+
+/// Per-source BFS summary shared by the path-length routines.
+struct SourceSums {
+    source: NodeIndex,
+    /// Σ distances to reachable targets
+    sum: u64,
+    /// Number of reachable targets (excluding the source)
+    pairs: u64,
+    /// Σ 1 / distance to reachable targets
+    inverse_sum: f64,
+    eccentricity: usize,
+}
+
+/// BFS from every node in `sources` across the rayon pool, reusing one
+/// distance buffer per worker.
+fn source_distance_sums(
+    graph: &Graph<usize, (), Undirected>,
+    sources: &[NodeIndex],
+) -> Vec<SourceSums> {
+    let n = graph.node_count();
+    sources
+        .par_iter()
+        .map_init(
+            || (vec![usize::MAX; n], VecDeque::new()),
+            |(dist, queue), &s| {
+                bfs_distances(graph, s, dist, queue);
+                let mut sum = 0u64;
+                let mut pairs = 0u64;
+                let mut inverse_sum = 0.0;
+                let mut ecc = 0;
+                for &d in dist.iter() {
+                    if d > 0 && d < usize::MAX {
+                        sum += d as u64;
+                        pairs += 1;
+                        inverse_sum += 1.0 / d as f64;
+                        ecc = ecc.max(d);
+                    }
                 }
-            }
+                SourceSums { source: s, sum, pairs, inverse_sum, eccentricity: ecc }
+            },
+        )
+        .collect()
+}
+
+/// All node indices in a seeded uniformly random order.
+fn shuffled_nodes(graph: &Graph<usize, (), Undirected>, rng: &mut StdRng) -> Vec<NodeIndex> {
+    let mut order: Vec<NodeIndex> = graph.node_indices().collect();
+    order.shuffle(rng);
+    order
+}
+
+/// Ratio estimate, delta-method SE and percentile bootstrap CI from
+/// per-source sums of a sample drawn from `n` nodes.
+fn summarize_estimate(
+    per_source: &[SourceSums],
+    n: usize,
+    opts: &EstimateOptions,
+    rng: &mut StdRng,
+) -> PathLengthEstimate {
+    let m = per_source.len();
+    let ratio = |idx: &mut dyn Iterator<Item = usize>| {
+        let (s, p) = idx.fold((0u64, 0u64), |(s, p), i| {
+            (s + per_source[i].sum, p + per_source[i].pairs)
+        });
+        if p > 0 { s as f64 / p as f64 } else { 0.0 }
+    };
+    let estimate = ratio(&mut (0..m));
+    let exhausted = m == n;
+
+    // sampling without replacement shrinks variance by the FPC (1 − m/n)
+    let fpc = if n > 0 { 1.0 - m as f64 / n as f64 } else { 0.0 };
+
+    // delta-method variance of a ratio estimator under SRSWOR
+    let standard_error = if m > 1 && !exhausted {
+        let mean_p = per_source.iter().map(|r| r.pairs as f64).sum::<f64>() / m as f64;
+        let resid_ss: f64 = per_source
+            .iter()
+            .map(|r| (r.sum as f64 - estimate * r.pairs as f64).powi(2))
+            .sum();
+        if mean_p > 0.0 {
+            (fpc * resid_ss / ((m - 1) as f64 * m as f64 * mean_p * mean_p)).sqrt()
+        } else {
+            0.0
         }
+    } else {
+        0.0
+    };
+
+    let (ci_low, ci_high) = if m > 1 && !exhausted && opts.bootstrap_replicates > 0 {
+        // rescaled bootstrap: shrink deviations by √fpc to match SRSWOR
+        let shrink = fpc.sqrt();
+        let mut reps: Vec<f64> = (0..opts.bootstrap_replicates)
+            .map(|_| {
+                let r = ratio(&mut (0..m).map(|_| rng.random_range(0..m)));
+                estimate + shrink * (r - estimate)
+            })
+            .collect();
+        reps.sort_by(f64::total_cmp);
+        let alpha = 1.0 - opts.confidence;
+        (
+            quantile_sorted(&reps, alpha / 2.0),
+            quantile_sorted(&reps, 1.0 - alpha / 2.0),
+        )
+    } else {
+        (estimate, estimate)
+    };
 
-        // Sum all nonzero, finite distances
-        for &d in &dist {
-            if d > 0 && d < usize::MAX {
-                total_dist += d as f64;
-                total_pairs += 1.0;
+    PathLengthEstimate {
+        estimate,
+        standard_error,
+        ci_low,
+        ci_high,
+        options: *opts,
+        sources: m,
+        exhausted,
+    }
+}
+
+/// Linearly interpolated `q`-quantile of an ascending, non-empty slice.
+fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+/// BFS from `start`, writing hop distances into `dist` (`usize::MAX` =
+/// unreachable). `dist` must have length `node_count`; it and `queue` are
+/// reset here so callers can reuse them across sources.
+fn bfs_distances(
+    graph: &Graph<usize, (), Undirected>,
+    start: NodeIndex,
+    dist: &mut [usize],
+    queue: &mut VecDeque<NodeIndex>,
+) {
+    dist.fill(usize::MAX);
+    queue.clear();
+    dist[start.index()] = 0;
+    queue.push_back(start);
+
+    while let Some(u) = queue.pop_front() {
+        let du = dist[u.index()];
+        for v in graph.neighbors(u) {
+            let vi = v.index();
+            if dist[vi] == usize::MAX {
+                dist[vi] = du + 1;
+                queue.push_back(v);
             }
         }
     }
-
-    total_dist / total_pairs
 }
 
 /// Result of the densest-subgraph peeling algorithm.
+#[derive(Debug, Clone, Serialize)]
 pub struct SubgraphResult {
     /// Node payloads included in the best subgraph
     pub nodes: Vec<usize>,
@@ -60,56 +375,921 @@ pub struct SubgraphResult {
     pub density: f64,
 }
 
+/// Densest-subgraph peeling together with its full trace, for plotting.
+#[derive(Debug, Clone, Serialize)]
+pub struct PeelingResult {
+    /// Best subgraph seen during peeling (same as [`densest_subgraph_peel`])
+    pub subgraph: SubgraphResult,
+    /// Node payloads in the order they were peeled
+    pub order: Vec<usize>,
+    /// `density_curve[i]` = density of the subgraph left just before
+    /// `order[i]` is removed (so `density_curve[0]` is the whole graph)
+    pub density_curve: Vec<f64>,
+}
+
 /// 2-approximation for max-density subgraph via peeling.
 /// 
 /// # Logic
-/// Repeatedly compute subgraph density, remove the lowest-degree node,
-/// track the iteration with highest density.
+/// Repeatedly remove the lowest-degree node and track the iteration with
+/// highest density; see [`densest_subgraph_peel_trace`] for the O(V+E)
+/// bucket-queue implementation.
 pub fn densest_subgraph_peel(graph: &Graph<usize, (), Undirected>) -> SubgraphResult {
-    let mut remaining: HashSet<NodeIndex> = graph.node_indices().collect();
+    densest_subgraph_peel_trace(graph).subgraph
+}
+
+/// Min-degree peeling in O(V+E), returning the best subgraph, the peeling
+/// order and the density curve.
+///
+/// # Logic
+/// Residual degrees live in a bucket queue (one doubly-linked list per
+/// degree). Each step pops a node from the lowest non-empty bucket and moves
+/// each surviving neighbour down one bucket; the minimum pointer only moves
+/// up by one per pop beyond what decrements moved it down, so total work is
+/// O(V+E). The remaining edge count is maintained alongside, giving the
+/// density |E_sub|/|V_sub| before every removal. Self-loops are ignored and
+/// parallel edges count with multiplicity.
+pub fn densest_subgraph_peel_trace(graph: &Graph<usize, (), Undirected>) -> PeelingResult {
+    let peel = min_degree_peel(graph);
+    let n = peel.order.len();
+
     let mut best_density = 0.0;
-    let mut best_nodes = Vec::new();
+    let mut best_start = n;
+    let mut density_curve = Vec::with_capacity(n);
+    for (i, &edges) in peel.edges_before.iter().enumerate() {
+        let density = edges as f64 / (n - i) as f64;
+        if density > best_density {
+            best_density = density;
+            best_start = i;
+        }
+        density_curve.push(density);
+    }
 
-    while !remaining.is_empty() {
-        let n = remaining.len() as f64;
-        let mut edge_count = 0;
-        // count edges internal to `remaining`
-        for &u in &remaining {
-            for v in graph.neighbors(u) {
-                if remaining.contains(&v) {
-                    edge_count += 1;
+    PeelingResult {
+        subgraph: SubgraphResult {
+            nodes: peel.order[best_start..].iter().map(|&u| graph[u]).collect(),
+            density: best_density,
+        },
+        order: peel.order.iter().map(|&u| graph[u]).collect(),
+        density_curve,
+    }
+}
+
+/// Dual certificate that no subgraph is denser than `edges / nodes`.
+///
+/// Each edge is split between its endpoints in units of `1/nodes`
+/// (a fractional orientation). If every node's total share is at most
+/// `edges`, i.e. its load is ≤ ρ* = edges/nodes, then for any node set S,
+/// |E(S)| ≤ Σ_{v∈S} load(v) ≤ ρ*·|S|, so no subgraph beats ρ*.
+#[derive(Debug, Clone, Serialize)]
+pub struct DensityCertificate {
+    /// Numerator of ρ*: edge count of the optimal subgraph
+    pub edges: usize,
+    /// Denominator of ρ*: node count of the optimal subgraph
+    pub nodes: usize,
+    /// `(u, v, share_u)` per graph edge: `share_u / nodes` of the edge is
+    /// charged to payload `u`, the remaining `(nodes − share_u) / nodes` to `v`
+    pub orientation: Vec<(usize, usize, usize)>,
+}
+
+impl DensityCertificate {
+    /// ρ* as a float.
+    pub fn density(&self) -> f64 {
+        if self.nodes == 0 { 0.0 } else { self.edges as f64 / self.nodes as f64 }
+    }
+
+    /// Check the certificate against `graph`: the orientation must cover
+    /// every non-loop edge exactly once and no node may carry more than ρ*.
+    pub fn verify(&self, graph: &Graph<usize, (), Undirected>) -> bool {
+        let q = self.nodes;
+        let mut expected: HashMap<(usize, usize), isize> = HashMap::new();
+        for e in graph.edge_indices() {
+            let (a, b) = graph.edge_endpoints(e).unwrap();
+            if a != b {
+                let (x, y) = (graph[a], graph[b]);
+                *expected.entry((x.min(y), x.max(y))).or_insert(0) += 1;
+            }
+        }
+
+        let mut load: HashMap<usize, usize> = HashMap::new();
+        for &(u, v, share_u) in &self.orientation {
+            if share_u > q {
+                return false;
+            }
+            match expected.get_mut(&(u.min(v), u.max(v))) {
+                Some(c) => *c -= 1,
+                None => return false,
+            }
+            *load.entry(u).or_insert(0) += share_u;
+            *load.entry(v).or_insert(0) += q - share_u;
+        }
+
+        expected.values().all(|&c| c == 0) && load.values().all(|&l| l <= self.edges)
+    }
+}
+
+/// Exact densest subgraph with its optimality certificate.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExactDensestResult {
+    /// Optimal subgraph (density = max |E_sub|/|V_sub|)
+    pub subgraph: SubgraphResult,
+    /// Fractional orientation proving optimality
+    pub certificate: DensityCertificate,
+    /// Number of max-flow parametric steps taken
+    pub iterations: usize,
+}
+
+/// Exact densest subgraph via Goldberg's max-flow formulation.
+///
+/// # Logic
+/// For a guess ρ = p/q, build Goldberg's network (scaled by q to stay
+/// integral): s→v with capacity m·q, v→t with m·q + 2p − q·d(v), and
+/// capacity q both ways along every edge. Every s-t cut (S ∪ {s}) costs
+/// m·q·n + 2(p|S| − q|E(S)|), so the min cut is below m·q·n iff some
+/// subgraph is denser than ρ, and its source side is such a subgraph.
+/// Starting from the peeling result, replace ρ by the density of that
+/// subgraph until no denser one exists (Dinkelbach iteration; densities
+/// strictly increase, usually within a handful of flows). The certificate
+/// comes from a second flow that routes every edge's q units to its
+/// endpoints with node capacity p. Self-loops are ignored.
+pub fn densest_subgraph_exact(graph: &Graph<usize, (), Undirected>) -> ExactDensestResult {
+    let n = graph.node_count();
+    let edges: Vec<(usize, usize)> = graph
+        .edge_indices()
+        .map(|e| graph.edge_endpoints(e).unwrap())
+        .filter(|(a, b)| a != b)
+        .map(|(a, b)| (a.index(), b.index()))
+        .collect();
+    let m = edges.len();
+    let mut deg = vec![0i64; n];
+    for &(u, v) in &edges {
+        deg[u] += 1;
+        deg[v] += 1;
+    }
+
+    // start from the peeling 2-approximation
+    let peel = densest_subgraph_peel_trace(graph);
+    let mut best: Vec<usize> = {
+        let payload_to_idx: HashMap<usize, usize> =
+            graph.node_indices().map(|u| (graph[u], u.index())).collect();
+        peel.subgraph.nodes.iter().map(|p| payload_to_idx[p]).collect()
+    };
+    let count_internal = |set: &[usize]| {
+        let mut inside = vec![false; n];
+        for &v in set {
+            inside[v] = true;
+        }
+        edges.iter().filter(|&&(u, v)| inside[u] && inside[v]).count()
+    };
+    let (mut p, mut q) = if best.is_empty() { (0, 1) } else { (count_internal(&best), best.len()) };
+
+    let mut iterations = 0;
+    loop {
+        iterations += 1;
+        let (s, t) = (n, n + 1);
+        let (pi, qi, mi) = (p as i64, q as i64, m as i64);
+        let mut net = FlowNetwork::new(n + 2);
+        for (v, &d) in deg.iter().enumerate() {
+            net.add_arc(s, v, mi * qi, 0);
+            net.add_arc(v, t, mi * qi + 2 * pi - qi * d, 0);
+        }
+        for &(u, v) in &edges {
+            net.add_arc(u, v, qi, qi);
+        }
+        let flow = net.max_flow(s, t);
+        if flow >= mi * qi * n as i64 {
+            break;
+        }
+        let side = net.source_side(s);
+        let denser: Vec<usize> = (0..n).filter(|&v| side[v]).collect();
+        let e_in = count_internal(&denser);
+        debug_assert!(e_in * q > p * denser.len());
+        p = e_in;
+        q = denser.len();
+        best = denser;
+    }
+
+    let certificate = orientation_certificate(&edges, n, p, q, |i| graph[NodeIndex::new(i)]);
+    let density = if best.is_empty() { 0.0 } else { p as f64 / q as f64 };
+    ExactDensestResult {
+        subgraph: SubgraphResult {
+            nodes: best.iter().map(|&v| graph[NodeIndex::new(v)]).collect(),
+            density,
+        },
+        certificate,
+        iterations,
+    }
+}
+
+/// Split every edge's `q` units between its endpoints with at most `p`
+/// units per node (max-flow on the edge–node incidence network).
+fn orientation_certificate(
+    edges: &[(usize, usize)],
+    n: usize,
+    p: usize,
+    q: usize,
+    payload: impl Fn(usize) -> usize,
+) -> DensityCertificate {
+    let m = edges.len();
+    let (s, t) = (m + n, m + n + 1);
+    let mut net = FlowNetwork::new(m + n + 2);
+    let mut to_u = Vec::with_capacity(m);
+    for (i, &(u, v)) in edges.iter().enumerate() {
+        net.add_arc(s, i, q as i64, 0);
+        to_u.push(net.add_arc(i, m + u, q as i64, 0));
+        net.add_arc(i, m + v, q as i64, 0);
+    }
+    for v in 0..n {
+        net.add_arc(m + v, t, p as i64, 0);
+    }
+    net.max_flow(s, t);
+
+    let orientation = edges
+        .iter()
+        .zip(&to_u)
+        .map(|(&(u, v), &arc)| (payload(u), payload(v), net.flow(arc) as usize))
+        .collect();
+    DensityCertificate { edges: p, nodes: q, orientation }
+}
+
+/// Per-pass summary of [`densest_subgraph_greedy_plus_plus`].
+#[derive(Debug, Clone, Serialize)]
+pub struct GreedyPlusPlusStep {
+    /// 1-based pass number
+    pub iteration: usize,
+    /// Densest subgraph seen during this pass
+    pub density: f64,
+    /// Densest subgraph seen in any pass so far (a lower bound on ρ*)
+    pub best_density: f64,
+    /// Tightest upper bound on ρ* so far: min over passes t of max_v ℓ(v)/t
+    pub upper_bound: f64,
+}
+
+/// Result of Greedy++: best subgraph plus the per-pass trace.
+#[derive(Debug, Clone, Serialize)]
+pub struct GreedyPlusPlusResult {
+    /// Densest subgraph found across all passes
+    pub subgraph: SubgraphResult,
+    /// One entry per pass; `best_density` and `upper_bound` bracket ρ*
+    pub trace: Vec<GreedyPlusPlusStep>,
+}
+
+/// Greedy++ densest subgraph (Boob et al., 2020): repeated load-weighted
+/// peeling that converges to the optimum as `iterations` grows.
+///
+/// # Logic
+/// Every node carries a load ℓ(v), initially 0. Each pass peels the node
+/// minimising ℓ(v) + residual degree, then adds that residual degree to
+/// ℓ(v); the first pass is plain Charikar peeling. Each pass is an edge
+/// orientation (edges charged to whichever endpoint leaves first), so
+/// max ℓ(v)/t after any t passes bounds ρ* from above, while the best subgraph
+/// seen bounds it from below. O(T·(V+E)·log V) with a lazy binary heap.
+/// Self-loops are ignored and parallel edges count with multiplicity.
+pub fn densest_subgraph_greedy_plus_plus(
+    graph: &Graph<usize, (), Undirected>,
+    iterations: usize,
+) -> GreedyPlusPlusResult {
+    let n = graph.node_count();
+    let base_deg: Vec<u64> = graph
+        .node_indices()
+        .map(|u| graph.neighbors(u).filter(|&v| v != u).count() as u64)
+        .collect();
+    let total_edges = base_deg.iter().sum::<u64>() / 2;
+
+    let mut load = vec![0u64; n];
+    let mut upper_bound = f64::INFINITY;
+    let mut best_density = 0.0;
+    let mut best_nodes: Vec<usize> = Vec::new();
+    let mut trace = Vec::with_capacity(iterations);
+
+    for iteration in 1..=iterations {
+        let mut deg = base_deg.clone();
+        let mut removed = vec![false; n];
+        let mut heap: BinaryHeap<Reverse<(u64, usize)>> =
+            (0..n).map(|v| Reverse((load[v] + deg[v], v))).collect();
+        let mut edges = total_edges;
+        let mut order = Vec::with_capacity(n);
+        let mut pass_density = 0.0;
+        let mut pass_start = n;
+
+        while let Some(Reverse((key, v))) = heap.pop() {
+            if removed[v] || key != load[v] + deg[v] {
+                continue; // stale entry
+            }
+            let density = edges as f64 / (n - order.len()) as f64;
+            if density > pass_density {
+                pass_density = density;
+                pass_start = order.len();
+            }
+
+            removed[v] = true;
+            order.push(v);
+            load[v] += deg[v];
+            edges -= deg[v];
+            for u in graph.neighbors(NodeIndex::new(v)) {
+                let ui = u.index();
+                if !removed[ui] {
+                    deg[ui] -= 1;
+                    heap.push(Reverse((load[ui] + deg[ui], ui)));
                 }
             }
         }
-        edge_count /= 2; // undirected double-count
-        let density = edge_count as f64 / n;
 
-        if density > best_density {
-            best_density = density;
-            best_nodes = remaining.iter().map(|&idx| graph[idx]).collect();
+        if pass_density > best_density {
+            best_density = pass_density;
+            best_nodes = order[pass_start..].iter().map(|&v| graph[NodeIndex::new(v)]).collect();
         }
+        let max_load = load.iter().copied().max().unwrap_or(0);
+        upper_bound = upper_bound.min(max_load as f64 / iteration as f64);
+        trace.push(GreedyPlusPlusStep {
+            iteration,
+            density: pass_density,
+            best_density,
+            upper_bound,
+        });
+    }
+
+    GreedyPlusPlusResult {
+        subgraph: SubgraphResult { nodes: best_nodes, density: best_density },
+        trace,
+    }
+}
+
+/// k-core decomposition of an undirected graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoreDecomposition {
+    /// node payload → core number (largest k whose k-core contains the node)
+    pub core_numbers: HashMap<usize, usize>,
+    /// Largest core number; every subgraph has a node of degree ≤ degeneracy
+    pub degeneracy: usize,
+    /// Node payloads in degeneracy order (the min-degree peeling order)
+    pub order: Vec<usize>,
+}
 
-        // peel off the minimum-degree node
-        if let Some((min_u, _)) = remaining
+impl CoreDecomposition {
+    /// Node payloads of the k-core (core number ≥ `k`), sorted ascending.
+    pub fn k_core_nodes(&self, k: usize) -> Vec<usize> {
+        let mut nodes: Vec<usize> = self
+            .core_numbers
             .iter()
-            .map(|&u| {
-                let deg = graph
-                    .neighbors(u)
-                    .filter(|v| remaining.contains(v))
-                    .count();
-                (u, deg)
-            })
-            .min_by_key(|&(_, deg)| deg)
-        {
-            remaining.remove(&min_u);
+            .filter(|&(_, &c)| c >= k)
+            .map(|(&v, _)| v)
+            .collect();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    /// The k-core of `graph` as an induced subgraph (empty if `k > degeneracy`).
+    /// `graph` must be the graph this decomposition was computed from.
+    pub fn k_core(
+        &self,
+        graph: &Graph<usize, (), Undirected>,
+        k: usize,
+    ) -> Graph<usize, (), Undirected> {
+        graph.filter_map(
+            |_, &v| (self.core_numbers[&v] >= k).then_some(v),
+            |_, &e| Some(e),
+        )
+    }
+
+    /// k-shell sizes: core number → number of nodes with exactly that core number.
+    pub fn core_size_distribution(&self) -> HashMap<usize, usize> {
+        let mut dist = HashMap::new();
+        for &c in self.core_numbers.values() {
+            *dist.entry(c).or_insert(0) += 1;
+        }
+        dist
+    }
+
+    /// k-core sizes: entry `k` is the number of nodes with core number ≥ k,
+    /// for k = 0..=degeneracy.
+    pub fn core_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.degeneracy + 1];
+        for &c in self.core_numbers.values() {
+            sizes[c] += 1;
+        }
+        for k in (0..self.degeneracy).rev() {
+            sizes[k] += sizes[k + 1];
+        }
+        sizes
+    }
+}
+
+/// Core number of every node, plus the degeneracy and degeneracy order.
+///
+/// # Inputs
+/// - `graph`: undirected graph with `usize` node payloads
+///
+/// # Output
+/// - [`CoreDecomposition`]; use its methods for k-core node sets, induced
+///   k-core subgraphs and core-size distributions
+///
+/// # Logic
+/// Batagelj–Zaversnik: the same O(V+E) bucket-queue min-degree peeling as
+/// [`densest_subgraph_peel_trace`]. A node's core number is the largest
+/// residual degree at which any node up to and including it was removed.
+/// Self-loops are ignored and parallel edges count with multiplicity.
+pub fn core_numbers(graph: &Graph<usize, (), Undirected>) -> CoreDecomposition {
+    let peel = min_degree_peel(graph);
+    let mut core_numbers = HashMap::with_capacity(peel.order.len());
+    let mut k = 0;
+    for (&v, &d) in peel.order.iter().zip(&peel.removal_degree) {
+        k = k.max(d);
+        core_numbers.insert(graph[v], k);
+    }
+    CoreDecomposition {
+        core_numbers,
+        degeneracy: k,
+        order: peel.order.iter().map(|&v| graph[v]).collect(),
+    }
+}
+
+/// k-truss decomposition: the trussness of every edge.
+///
+/// The k-truss is the largest subgraph in which every edge lies in at least
+/// k − 2 triangles; an edge's trussness is the largest such k (≥ 2).
+#[derive(Debug, Clone)]
+pub struct TrussDecomposition {
+    /// (smaller payload, larger payload) → trussness
+    pub trussness: HashMap<(usize, usize), usize>,
+    /// Largest trussness of any edge (0 for an edgeless graph)
+    pub max_truss: usize,
+}
+
+impl TrussDecomposition {
+    /// Edges of the k-truss as `(u, v)` payload pairs with `u < v`, sorted.
+    pub fn k_truss_edges(&self, k: usize) -> Vec<(usize, usize)> {
+        let mut edges: Vec<_> = self
+            .trussness
+            .iter()
+            .filter(|&(_, &t)| t >= k)
+            .map(|(&e, _)| e)
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// The k-truss of `graph`: edges of trussness ≥ `k` and their endpoints.
+    /// `graph` must be the graph this decomposition was computed from.
+    pub fn k_truss(
+        &self,
+        graph: &Graph<usize, (), Undirected>,
+        k: usize,
+    ) -> Graph<usize, (), Undirected> {
+        let edges = self.k_truss_edges(k);
+        let mut keep: Vec<usize> = edges.iter().flat_map(|&(u, v)| [u, v]).collect();
+        keep.sort_unstable();
+        keep.dedup();
+        graph.filter_map(
+            |_, &v| keep.binary_search(&v).is_ok().then_some(v),
+            |e, &w| {
+                let (a, b) = graph.edge_endpoints(e)?;
+                let (a, b) = (graph[a], graph[b]);
+                let key = (a.min(b), a.max(b));
+                (self.trussness.get(&key).copied().unwrap_or(0) >= k).then_some(w)
+            },
+        )
+    }
+
+    /// Trussness → number of edges with exactly that trussness.
+    pub fn truss_size_distribution(&self) -> HashMap<usize, usize> {
+        let mut dist = HashMap::new();
+        for &t in self.trussness.values() {
+            *dist.entry(t).or_insert(0) += 1;
+        }
+        dist
+    }
+}
+
+/// Trussness of every edge from triangle support.
+///
+/// # Inputs
+/// - `graph`: undirected graph with `usize` node payloads
+///
+/// # Output
+/// - [`TrussDecomposition`]; `k_truss(graph, max_truss)` is the maximal truss
+///
+/// # Logic
+/// Wang–Cheng: the support of edge (u, v) is |N(u) ∩ N(v)|, found by merging
+/// sorted adjacency lists. Edges are then peeled in order of least support
+/// (lazy binary heap); removing (u, v) decrements the support of (u, w) and
+/// (v, w) for every surviving triangle u–v–w. An edge's trussness is the
+/// largest support + 2 seen among edges removed up to and including it.
+/// O(E^1.5 + E·log E). The graph is treated as simple: self-loops are
+/// ignored and parallel edges are merged.
+pub fn truss_decomposition(graph: &Graph<usize, (), Undirected>) -> TrussDecomposition {
+    let n = graph.node_count();
+
+    // simple sorted adjacency with edge ids: adj[u] = [(v, edge id)]
+    let edges = simple_edges(graph);
+    let mut adj: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    for (id, &(u, v)) in edges.iter().enumerate() {
+        adj[u].push((v, id));
+        adj[v].push((u, id));
+    }
+    for list in &mut adj {
+        list.sort_unstable();
+    }
+
+    // calls f(edge u–w, edge v–w) for every common neighbour w of u and v
+    let for_each_triangle = |u: usize, v: usize, f: &mut dyn FnMut(usize, usize)| {
+        let (a, b) = (&adj[u], &adj[v]);
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].0.cmp(&b[j].0) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    f(a[i].1, b[j].1);
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+    };
+
+    let mut support: Vec<usize> = edges
+        .par_iter()
+        .map(|&(u, v)| {
+            let mut count = 0;
+            for_each_triangle(u, v, &mut |_, _| count += 1);
+            count
+        })
+        .collect();
+
+    let mut alive = vec![true; edges.len()];
+    let mut truss = vec![0; edges.len()];
+    let mut heap: BinaryHeap<Reverse<(usize, usize)>> =
+        support.iter().enumerate().map(|(e, &s)| Reverse((s, e))).collect();
+    let mut level = 0;
+    while let Some(Reverse((sup, e))) = heap.pop() {
+        if !alive[e] || sup != support[e] {
+            continue; // stale entry
+        }
+        level = level.max(sup + 2);
+        truss[e] = level;
+        alive[e] = false;
+        let (u, v) = edges[e];
+        for_each_triangle(u, v, &mut |uw, vw| {
+            if alive[uw] && alive[vw] {
+                for f in [uw, vw] {
+                    support[f] -= 1;
+                    heap.push(Reverse((support[f], f)));
+                }
+            }
+        });
+    }
+
+    let trussness = edges
+        .iter()
+        .zip(&truss)
+        .map(|(&(u, v), &t)| {
+            let (a, b) = (graph[NodeIndex::new(u)], graph[NodeIndex::new(v)]);
+            ((a.min(b), a.max(b)), t)
+        })
+        .collect();
+    TrussDecomposition { trussness, max_truss: level }
+}
+
+/// Per-node and total triangle counts.
+#[derive(Debug, Clone, Serialize)]
+pub struct TriangleCounts {
+    /// node payload → number of triangles through the node
+    pub per_node: HashMap<usize, usize>,
+    /// Number of distinct triangles in the graph
+    pub total: usize,
+}
+
+/// Exact triangle counts per node and in total.
+///
+/// # Logic
+/// Degree ordering: rank nodes by (degree, index) and orient every edge
+/// from lower to higher rank, so each node keeps at most O(√E) out-edges.
+/// Each triangle is then found exactly once, from its lowest-ranked corner,
+/// by marking that corner's out-neighbours and scanning theirs.
+/// O(E^1.5) time. The graph is treated as simple: self-loops are ignored
+/// and parallel edges are merged.
+pub fn triangle_counts(graph: &Graph<usize, (), Undirected>) -> TriangleCounts {
+    let n = graph.node_count();
+    let edges = simple_edges(graph);
+    let mut deg = vec![0usize; n];
+    for &(u, v) in &edges {
+        deg[u] += 1;
+        deg[v] += 1;
+    }
+    let before = |a: usize, b: usize| (deg[a], a) < (deg[b], b);
+    let mut out: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for &(u, v) in &edges {
+        if before(u, v) {
+            out[u].push(v);
         } else {
-            break;
+            out[v].push(u);
+        }
+    }
+
+    let mut count = vec![0usize; n];
+    let mut total = 0;
+    let mut mark = vec![false; n];
+    for u in 0..n {
+        for &v in &out[u] {
+            mark[v] = true;
+        }
+        for &v in &out[u] {
+            for &w in &out[v] {
+                if mark[w] {
+                    count[u] += 1;
+                    count[v] += 1;
+                    count[w] += 1;
+                    total += 1;
+                }
+            }
+        }
+        for &v in &out[u] {
+            mark[v] = false;
         }
     }
 
-    SubgraphResult {
-        nodes: best_nodes,
-        density: best_density,
+    TriangleCounts {
+        per_node: graph.node_indices().map(|u| (graph[u], count[u.index()])).collect(),
+        total,
+    }
+}
+
+/// Clustering summary built on [`triangle_counts`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusteringStats {
+    /// Both the per-node triangle counts and the graph total, from [`triangle_counts`]
+    pub triangles: TriangleCounts,
+    /// node payload → local clustering coefficient (0 for degree < 2)
+    pub local_clustering: HashMap<usize, f64>,
+    /// Mean local clustering over all nodes
+    pub average_clustering: f64,
+    /// Global transitivity: 3 × triangles / connected triples
+    pub transitivity: f64,
+    /// C(k): degree k → mean local clustering of degree-k nodes (k ≥ 2)
+    pub clustering_by_degree: HashMap<usize, f64>,
+}
+
+/// Local and global clustering coefficients, plus the C(k) curve.
+///
+/// # Inputs
+/// - `graph`: undirected graph with `usize` node payloads
+///
+/// # Logic
+/// With t(v) triangles through v and simple degree d(v), the local
+/// coefficient is C(v) = 2·t(v) / (d(v)·(d(v) − 1)); the average is taken
+/// over every node, counting degree < 2 as 0. Transitivity is
+/// Σ t(v) / Σ d(v)(d(v) − 1)/2, i.e. closed over connected triples.
+/// C(k) averages C(v) over the nodes of each degree k ≥ 2.
+pub fn clustering_stats(graph: &Graph<usize, (), Undirected>) -> ClusteringStats {
+    let triangles = triangle_counts(graph);
+    let mut deg = vec![0usize; graph.node_count()];
+    for (u, v) in simple_edges(graph) {
+        deg[u] += 1;
+        deg[v] += 1;
+    }
+
+    let mut local_clustering = HashMap::with_capacity(deg.len());
+    let mut by_degree: HashMap<usize, (f64, usize)> = HashMap::new();
+    let mut closed = 0u64;
+    let mut triples = 0u64;
+    for u in graph.node_indices() {
+        let d = deg[u.index()];
+        let t = triangles.per_node[&graph[u]];
+        let pairs = d * d.saturating_sub(1) / 2;
+        let c = if pairs > 0 { t as f64 / pairs as f64 } else { 0.0 };
+        local_clustering.insert(graph[u], c);
+        closed += t as u64;
+        triples += pairs as u64;
+        if d >= 2 {
+            let entry = by_degree.entry(d).or_insert((0.0, 0));
+            entry.0 += c;
+            entry.1 += 1;
+        }
+    }
+
+    let n = local_clustering.len();
+    ClusteringStats {
+        average_clustering: if n > 0 { local_clustering.values().sum::<f64>() / n as f64 } else { 0.0 },
+        transitivity: if triples > 0 { closed as f64 / triples as f64 } else { 0.0 },
+        clustering_by_degree: by_degree.into_iter().map(|(k, (sum, cnt))| (k, sum / cnt as f64)).collect(),
+        local_clustering,
+        triangles,
+    }
+}
+
+/// Distinct non-loop edges as `(smaller index, larger index)`, sorted.
+fn simple_edges(graph: &Graph<usize, (), Undirected>) -> Vec<(usize, usize)> {
+    let mut edges: Vec<(usize, usize)> = graph
+        .edge_indices()
+        .filter_map(|e| graph.edge_endpoints(e))
+        .map(|(a, b)| (a.index().min(b.index()), a.index().max(b.index())))
+        .filter(|&(u, v)| u != v)
+        .collect();
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+/// Full min-degree peeling sequence.
+struct Peel {
+    /// Nodes in removal order
+    order: Vec<NodeIndex>,
+    /// Residual edge count just before `order[i]` was removed
+    edges_before: Vec<usize>,
+    /// Residual degree of `order[i]` when it was removed
+    removal_degree: Vec<usize>,
+}
+
+/// Repeatedly remove a minimum-residual-degree node using a bucket queue.
+fn min_degree_peel(graph: &Graph<usize, (), Undirected>) -> Peel {
+    const NONE: usize = usize::MAX;
+    let n = graph.node_count();
+
+    let mut deg: Vec<usize> = graph
+        .node_indices()
+        .map(|u| graph.neighbors(u).filter(|&v| v != u).count())
+        .collect();
+    let max_deg = deg.iter().copied().max().unwrap_or(0);
+    let mut edges = deg.iter().sum::<usize>() / 2;
+
+    // bucket d holds the nodes of residual degree d as a doubly-linked list
+    let mut head = vec![NONE; max_deg + 1];
+    let mut next = vec![NONE; n];
+    let mut prev = vec![NONE; n];
+    let unlink = |head: &mut [usize], next: &mut [usize], prev: &mut [usize], v: usize, d: usize| {
+        if prev[v] != NONE {
+            next[prev[v]] = next[v];
+        } else {
+            head[d] = next[v];
+        }
+        if next[v] != NONE {
+            prev[next[v]] = prev[v];
+        }
+    };
+    let push = |head: &mut [usize], next: &mut [usize], prev: &mut [usize], v: usize, d: usize| {
+        prev[v] = NONE;
+        next[v] = head[d];
+        if head[d] != NONE {
+            prev[head[d]] = v;
+        }
+        head[d] = v;
+    };
+    for (v, &d) in deg.iter().enumerate() {
+        push(&mut head, &mut next, &mut prev, v, d);
+    }
+
+    let mut removed = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut edges_before = Vec::with_capacity(n);
+    let mut removal_degree = Vec::with_capacity(n);
+    let mut cur = 0;
+    for _ in 0..n {
+        while head[cur] == NONE {
+            cur += 1;
+        }
+        let v = head[cur];
+        unlink(&mut head, &mut next, &mut prev, v, cur);
+        removed[v] = true;
+        order.push(NodeIndex::new(v));
+        edges_before.push(edges);
+        removal_degree.push(cur);
+        edges -= deg[v];
+
+        for u in graph.neighbors(NodeIndex::new(v)) {
+            let ui = u.index();
+            if removed[ui] {
+                continue;
+            }
+            unlink(&mut head, &mut next, &mut prev, ui, deg[ui]);
+            deg[ui] -= 1;
+            push(&mut head, &mut next, &mut prev, ui, deg[ui]);
+            cur = cur.min(deg[ui]);
+        }
+    }
+
+    Peel { order, edges_before, removal_degree }
+}
+
+/// Integer-capacity flow network solved with Dinic's algorithm.
+///
+/// Arcs are stored in pairs (`2k` forward, `2k + 1` reverse) so the
+/// residual partner of arc `e` is `e ^ 1`.
+struct FlowNetwork {
+    adj: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    cap: Vec<i64>,
+    initial: Vec<i64>,
+}
+
+impl FlowNetwork {
+    fn new(n: usize) -> Self {
+        FlowNetwork { adj: vec![Vec::new(); n], to: Vec::new(), cap: Vec::new(), initial: Vec::new() }
+    }
+
+    /// Add arc `u → v` with capacity `c` and reverse capacity `rc`;
+    /// returns the forward arc id.
+    fn add_arc(&mut self, u: usize, v: usize, c: i64, rc: i64) -> usize {
+        let e = self.to.len();
+        self.adj[u].push(e);
+        self.to.push(v);
+        self.cap.push(c);
+        self.initial.push(c);
+        self.adj[v].push(e + 1);
+        self.to.push(u);
+        self.cap.push(rc);
+        self.initial.push(rc);
+        e
+    }
+
+    /// Flow currently pushed along forward arc `e`.
+    fn flow(&self, e: usize) -> i64 {
+        self.initial[e] - self.cap[e]
+    }
+
+    /// Maximum s-t flow (Dinic: BFS level graph + iterative blocking flow).
+    fn max_flow(&mut self, s: usize, t: usize) -> i64 {
+        let n = self.adj.len();
+        let mut total = 0;
+        let mut level = vec![usize::MAX; n];
+        let mut it = vec![0usize; n];
+        let mut queue = VecDeque::new();
+        let mut path: Vec<usize> = Vec::new();
+
+        loop {
+            level.fill(usize::MAX);
+            level[s] = 0;
+            queue.push_back(s);
+            while let Some(u) = queue.pop_front() {
+                for &e in &self.adj[u] {
+                    let v = self.to[e];
+                    if self.cap[e] > 0 && level[v] == usize::MAX {
+                        level[v] = level[u] + 1;
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if level[t] == usize::MAX {
+                return total;
+            }
+
+            it.fill(0);
+            path.clear();
+            let mut u = s;
+            loop {
+                if u == t {
+                    let f = path.iter().map(|&e| self.cap[e]).min().unwrap_or(0);
+                    for &e in &path {
+                        self.cap[e] -= f;
+                        self.cap[e ^ 1] += f;
+                    }
+                    total += f;
+                    path.clear();
+                    u = s;
+                    continue;
+                }
+                // advance along the first usable level-graph arc
+                let mut advanced = false;
+                while it[u] < self.adj[u].len() {
+                    let e = self.adj[u][it[u]];
+                    let v = self.to[e];
+                    if self.cap[e] > 0 && level[v] == level[u] + 1 {
+                        path.push(e);
+                        u = v;
+                        advanced = true;
+                        break;
+                    }
+                    it[u] += 1;
+                }
+                if advanced {
+                    continue;
+                }
+                // dead end: retreat
+                if u == s {
+                    break;
+                }
+                level[u] = usize::MAX;
+                let e = path.pop().unwrap();
+                u = self.to[e ^ 1];
+                it[u] += 1;
+            }
+        }
+    }
+
+    /// Nodes reachable from `s` in the residual graph (min-cut source side).
+    fn source_side(&self, s: usize) -> Vec<bool> {
+        let mut seen = vec![false; self.adj.len()];
+        let mut stack = vec![s];
+        seen[s] = true;
+        while let Some(u) = stack.pop() {
+            for &e in &self.adj[u] {
+                let v = self.to[e];
+                if self.cap[e] > 0 && !seen[v] {
+                    seen[v] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        seen
     }
 }
 
@@ -158,98 +1338,1105 @@ pub fn two_hop_distribution(
 
 /// Compute closeness centrality: C(u) = (N-1) / Σ_v d(u,v).
 /// Returns map from node payload → centrality score.
+///
+/// The sum only runs over nodes reachable from u, so on a disconnected
+/// graph nodes of small components score high; this is
+/// [`ClosenessVariant::Reachable`] of [`closeness_centrality_with`], which
+/// also offers the Wasserman–Faust and harmonic variants.
 pub fn closeness_centrality(
     graph: &Graph<usize, (), Undirected>,
+) -> HashMap<usize, f64> {
+    closeness_centrality_with(graph, ClosenessVariant::Reachable)
+}
+
+/// Closeness formula used by [`closeness_centrality_with`]. With r(u) the
+/// number of nodes reachable from u (excluding u) and N the node count:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClosenessVariant {
+    /// (N − 1) / Σ d(u, v) over reachable v — the original definition.
+    Reachable,
+    /// Wasserman–Faust: (r(u) / (N − 1)) · (r(u) / Σ d(u, v)); the
+    /// component-local closeness scaled by the fraction of nodes reached,
+    /// so nodes in small components no longer score high.
+    WassermanFaust,
+    /// Harmonic centrality: Σ 1 / d(u, v), where unreachable nodes add 0.
+    Harmonic,
+}
+
+/// Closeness centrality in the chosen [`ClosenessVariant`].
+///
+/// # Logic
+/// One BFS per node across the rayon pool (reusing one distance buffer per
+/// worker), then the per-source distance sum, reachable count and Σ 1/d are
+/// combined per the variant. Isolated nodes score 0 in every variant.
+/// O(V·(V+E)).
+pub fn closeness_centrality_with(
+    graph: &Graph<usize, (), Undirected>,
+    variant: ClosenessVariant,
 ) -> HashMap<usize, f64> {
     let n = graph.node_count() as f64;
-    let mut closeness = HashMap::new();
+    let sources: Vec<NodeIndex> = graph.node_indices().collect();
+    source_distance_sums(graph, &sources)
+        .into_iter()
+        .map(|ss| {
+            let (sum, reached) = (ss.sum as f64, ss.pairs as f64);
+            let c = match variant {
+                _ if ss.sum == 0 => 0.0,
+                ClosenessVariant::Reachable => (n - 1.0) / sum,
+                ClosenessVariant::WassermanFaust => (reached / (n - 1.0)) * (reached / sum),
+                ClosenessVariant::Harmonic => ss.inverse_sum,
+            };
+            (graph[ss.source], c)
+        })
+        .collect()
+}
 
-    for u in graph.node_indices() {
-        let mut dist = vec![usize::MAX; graph.node_count()];
-        let mut queue = VecDeque::new();
-        dist[u.index()] = 0;
-        queue.push_back(u);
+/// Options for [`pagerank`] and [`personalized_pagerank`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PageRankOptions {
+    /// Probability of following an edge rather than teleporting
+    pub damping: f64,
+    /// Stop once the L1 change between iterations is below this
+    pub tolerance: f64,
+    /// Give up (with `converged: false`) after this many iterations
+    pub max_iterations: usize,
+}
 
-        while let Some(v) = queue.pop_front() {
-            let dv = dist[v.index()];
-            for w in graph.neighbors(v) {
-                let wi = w.index();
-                if dist[wi] == usize::MAX {
-                    dist[wi] = dv + 1;
-                    queue.push_back(w);
-                }
-            }
+impl Default for PageRankOptions {
+    fn default() -> Self {
+        PageRankOptions { damping: 0.85, tolerance: 1e-9, max_iterations: 200 }
+    }
+}
+
+/// How an iterative centrality computation ended.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConvergenceReport {
+    /// Iterations actually run
+    pub iterations: usize,
+    /// Whether the residual fell below the tolerance within the iteration limit
+    pub converged: bool,
+    /// L1 change of the final iteration
+    pub residual: f64,
+}
+
+/// PageRank scores (summing to 1) with their convergence report.
+#[derive(Debug, Clone, Serialize)]
+pub struct PageRankResult {
+    /// node payload → score
+    pub scores: HashMap<usize, f64>,
+    /// How the power iteration ended
+    pub convergence: ConvergenceReport,
+}
+
+/// PageRank by power iteration with uniform teleportation.
+///
+/// # Logic
+/// Each undirected edge is followed in both directions (parallel edges
+/// with multiplicity): x'(v) = d·(Σ_{u~v} x(u)/deg(u) + D/N) + (1 − d)/N,
+/// where D is the mass on isolated nodes, which is spread like a teleport.
+/// Iterates from the uniform vector until the L1 change is below
+/// `tolerance` or `max_iterations` is reached; each iteration pulls over
+/// the nodes in parallel. O(iterations·(V+E)).
+pub fn pagerank(graph: &Graph<usize, (), Undirected>, opts: &PageRankOptions) -> PageRankResult {
+    let n = graph.node_count();
+    let teleport = vec![1.0 / n.max(1) as f64; n];
+    pagerank_power(graph, &teleport, opts)
+}
+
+/// Personalized PageRank: teleports (and isolated-node mass) go uniformly
+/// to the `seeds` node payloads instead of to every node.
+/// Returns `None` if none of the seeds is in the graph; unknown payloads
+/// are otherwise ignored.
+pub fn personalized_pagerank(
+    graph: &Graph<usize, (), Undirected>,
+    seeds: &[usize],
+    opts: &PageRankOptions,
+) -> Option<PageRankResult> {
+    let mut teleport: Vec<f64> = graph
+        .node_indices()
+        .map(|u| if seeds.contains(&graph[u]) { 1.0 } else { 0.0 })
+        .collect();
+    let count: f64 = teleport.iter().sum();
+    if count == 0.0 {
+        return None;
+    }
+    teleport.iter_mut().for_each(|p| *p /= count);
+    Some(pagerank_power(graph, &teleport, opts))
+}
+
+/// Power iteration shared by the PageRank variants; `teleport` sums to 1.
+fn pagerank_power(
+    graph: &Graph<usize, (), Undirected>,
+    teleport: &[f64],
+    opts: &PageRankOptions,
+) -> PageRankResult {
+    let n = graph.node_count();
+    let adj = Adjacency::new(graph);
+    let degree: Vec<f64> = (0..n).map(|u| adj.neighbors(u).len() as f64).collect();
+    let d = opts.damping;
+
+    let mut x = teleport.to_vec();
+    let mut convergence = ConvergenceReport { iterations: 0, converged: n == 0, residual: 0.0 };
+    while !convergence.converged && convergence.iterations < opts.max_iterations {
+        let dangling: f64 = (0..n).filter(|&u| degree[u] == 0.0).map(|u| x[u]).sum();
+        let next: Vec<f64> = (0..n)
+            .into_par_iter()
+            .map(|v| {
+                let inflow: f64 = adj.neighbors(v).iter().map(|&u| x[u] / degree[u]).sum();
+                d * (inflow + dangling * teleport[v]) + (1.0 - d) * teleport[v]
+            })
+            .collect();
+        convergence.residual = next.iter().zip(&x).map(|(a, b)| (a - b).abs()).sum();
+        convergence.iterations += 1;
+        convergence.converged = convergence.residual < opts.tolerance;
+        x = next;
+    }
+
+    PageRankResult {
+        scores: graph.node_indices().map(|u| (graph[u], x[u.index()])).collect(),
+        convergence,
+    }
+}
+
+/// Stopping rule for [`eigenvector_centrality`] and [`katz_centrality`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PowerIterationOptions {
+    /// Stop once the L1 change of the unit-length iterate is below this
+    pub tolerance: f64,
+    /// Give up (with `converged: false`) after this many iterations
+    pub max_iterations: usize,
+}
+
+impl Default for PowerIterationOptions {
+    fn default() -> Self {
+        PowerIterationOptions { tolerance: 1e-9, max_iterations: 1000 }
+    }
+}
+
+/// Options for [`katz_centrality`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct KatzOptions {
+    /// Attenuation factor α; must be below 1/λ_max. `None` picks 0.9/λ_max.
+    pub alpha: Option<f64>,
+    /// Constant weight β every node receives
+    pub beta: f64,
+    /// Stopping rule for both the λ_max estimate and the Katz iteration
+    pub iteration: PowerIterationOptions,
+}
+
+impl Default for KatzOptions {
+    fn default() -> Self {
+        KatzOptions { alpha: None, beta: 1.0, iteration: PowerIterationOptions::default() }
+    }
+}
+
+/// Why a centrality could not be computed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CentralityError {
+    /// The graph has no nodes.
+    EmptyGraph,
+    /// Path sampling needs 0 < ε < 1 and 0 < δ < 1.
+    InvalidSampling { epsilon: f64, delta: f64 },
+    /// The leading eigenvector is only unique on a connected graph.
+    Disconnected { components: usize },
+    /// Katz only converges for α < 1/λ_max.
+    AttenuationTooLarge { alpha: f64, spectral_radius: f64 },
+}
+
+impl fmt::Display for CentralityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CentralityError::EmptyGraph => write!(f, "graph has no nodes"),
+            CentralityError::InvalidSampling { epsilon, delta } => write!(
+                f,
+                "path sampling needs 0 < epsilon < 1 and 0 < delta < 1 (got epsilon = {}, delta = {})",
+                epsilon, delta
+            ),
+            CentralityError::Disconnected { components } => write!(
+                f,
+                "graph has {} connected components; eigenvector centrality needs a connected graph",
+                components
+            ),
+            CentralityError::AttenuationTooLarge { alpha, spectral_radius } => write!(
+                f,
+                "Katz attenuation {} is not below 1/λ_max = {:.6} (λ_max = {:.4})",
+                alpha,
+                1.0 / spectral_radius,
+                spectral_radius
+            ),
         }
+    }
+}
+
+impl Error for CentralityError {}
+
+/// Eigenvector centrality scores (unit L2 norm) and the leading eigenvalue.
+#[derive(Debug, Clone, Serialize)]
+pub struct EigenvectorResult {
+    /// node payload → score
+    pub scores: HashMap<usize, f64>,
+    /// λ_max, the Rayleigh quotient of the final iterate
+    pub eigenvalue: f64,
+    /// How the power iteration ended
+    pub convergence: ConvergenceReport,
+}
 
-        let sum_d: usize = dist.iter().filter(|&&d| d > 0 && d < usize::MAX).sum();
-        let c = if sum_d > 0 { (n - 1.0) / (sum_d as f64) } else { 0.0 };
-        closeness.insert(graph[u], c);
+/// Katz centrality scores (unit L2 norm) with the parameters actually used.
+#[derive(Debug, Clone, Serialize)]
+pub struct KatzResult {
+    /// node payload → score
+    pub scores: HashMap<usize, f64>,
+    /// Attenuation factor α used (the 0.9/λ_max default if none was given)
+    pub alpha: f64,
+    /// Constant weight β used
+    pub beta: f64,
+    /// λ_max estimated by power iteration, bounding α
+    pub spectral_radius: f64,
+    /// How the Katz fixed-point iteration ended
+    pub convergence: ConvergenceReport,
+}
+
+/// Eigenvector centrality: the leading eigenvector of the adjacency matrix.
+///
+/// # Logic
+/// Power iteration on A + I (the shift keeps bipartite graphs from
+/// oscillating without changing the eigenvector), starting from the
+/// all-ones vector and rescaling to unit L2 norm each step; parallel edges
+/// count with multiplicity. Fails with `Disconnected` when the graph has
+/// more than one component, since the eigenvector is then not unique and
+/// small components score 0. O(iterations·(V+E)).
+pub fn eigenvector_centrality(
+    graph: &Graph<usize, (), Undirected>,
+    opts: &PowerIterationOptions,
+) -> Result<EigenvectorResult, CentralityError> {
+    match petgraph::algo::connected_components(graph) {
+        0 => return Err(CentralityError::EmptyGraph),
+        1 => {}
+        components => return Err(CentralityError::Disconnected { components }),
+    }
+    let adj = Adjacency::new(graph);
+    let (x, eigenvalue, convergence) = leading_eigenvector(&adj, graph.node_count(), opts);
+    Ok(EigenvectorResult {
+        scores: graph.node_indices().map(|u| (graph[u], x[u.index()])).collect(),
+        eigenvalue,
+        convergence,
+    })
+}
+
+/// Katz centrality: x = α·A·x + β·1, i.e. walks of every length from each
+/// node, the ones of length k weighted by α^k.
+///
+/// # Logic
+/// Estimates λ_max with the eigenvector power iteration (any graph: on a
+/// disconnected one it converges to the largest component's eigenvalue),
+/// rejects α ≥ 1/λ_max (up to the tolerance) with `AttenuationTooLarge`,
+/// then iterates the fixed point from β·1 until the L1 change of the
+/// unit-length iterate is below the tolerance. Scores are rescaled to unit
+/// L2 norm so they compare with [`eigenvector_centrality`], which they
+/// approach as α → 1/λ_max. O(iterations·(V+E)).
+pub fn katz_centrality(
+    graph: &Graph<usize, (), Undirected>,
+    opts: &KatzOptions,
+) -> Result<KatzResult, CentralityError> {
+    let n = graph.node_count();
+    if n == 0 {
+        return Err(CentralityError::EmptyGraph);
+    }
+    let adj = Adjacency::new(graph);
+    let (_, spectral_radius, _) = leading_eigenvector(&adj, n, &opts.iteration);
+    let alpha = match opts.alpha {
+        Some(alpha) => alpha,
+        None if spectral_radius > 0.0 => 0.9 / spectral_radius,
+        None => 0.0,
+    };
+    // the Rayleigh quotient approaches λ_max from below, so leave a margin
+    if alpha * spectral_radius >= 1.0 - opts.iteration.tolerance {
+        return Err(CentralityError::AttenuationTooLarge { alpha, spectral_radius });
+    }
+
+    let beta = opts.beta;
+    let mut x = vec![beta; n];
+    let mut unit = unit_l2(&x);
+    let mut convergence = ConvergenceReport { iterations: 0, converged: false, residual: 0.0 };
+    while !convergence.converged && convergence.iterations < opts.iteration.max_iterations {
+        x = (0..n)
+            .into_par_iter()
+            .map(|v| alpha * adj.neighbors(v).iter().map(|&u| x[u]).sum::<f64>() + beta)
+            .collect();
+        let next = unit_l2(&x);
+        convergence.residual = l1_distance(&next, &unit);
+        convergence.iterations += 1;
+        convergence.converged = convergence.residual < opts.iteration.tolerance;
+        unit = next;
+    }
+
+    Ok(KatzResult {
+        scores: graph.node_indices().map(|u| (graph[u], unit[u.index()])).collect(),
+        alpha,
+        beta,
+        spectral_radius,
+        convergence,
+    })
+}
+
+/// Power iteration on A + I from the all-ones vector → (unit-length
+/// iterate, Rayleigh quotient xᵀAx, convergence).
+fn leading_eigenvector(
+    adj: &Adjacency,
+    n: usize,
+    opts: &PowerIterationOptions,
+) -> (Vec<f64>, f64, ConvergenceReport) {
+    let mut x = unit_l2(&vec![1.0; n]);
+    let mut convergence = ConvergenceReport { iterations: 0, converged: false, residual: 0.0 };
+    while !convergence.converged && convergence.iterations < opts.max_iterations {
+        let next: Vec<f64> = (0..n)
+            .into_par_iter()
+            .map(|v| x[v] + adj.neighbors(v).iter().map(|&u| x[u]).sum::<f64>())
+            .collect();
+        let next = unit_l2(&next);
+        convergence.residual = l1_distance(&next, &x);
+        convergence.iterations += 1;
+        convergence.converged = convergence.residual < opts.tolerance;
+        x = next;
+    }
+    let rayleigh = (0..n)
+        .map(|v| x[v] * adj.neighbors(v).iter().map(|&u| x[u]).sum::<f64>())
+        .sum();
+    (x, rayleigh, convergence)
+}
+
+/// `x` rescaled to unit L2 norm (unchanged if it is all zeros).
+fn unit_l2(x: &[f64]) -> Vec<f64> {
+    let norm = x.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm == 0.0 {
+        return x.to_vec();
     }
+    x.iter().map(|v| v / norm).collect()
+}
 
-    closeness
+fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
 }
 
 /// Compute betweenness centrality (Brandes’ algorithm).
 /// Returns map from node payload → betweenness score.
+///
+/// # Logic
+/// One BFS + dependency accumulation per source, spread across the rayon
+/// pool. Each worker keeps dense index-based buffers (distance, path count,
+/// dependency, BFS order, shortest-path DAG arcs) and its own score vector;
+/// the vectors are summed at the end. Scores are unnormalised and count
+/// every ordered (s, t) pair, so undirected pairs contribute twice; see
+/// [`betweenness_centrality_with`] for normalised and subset variants.
+/// O(V·E) time, O(threads·(V + E)) memory.
 pub fn betweenness_centrality(
     graph: &Graph<usize, (), Undirected>,
 ) -> HashMap<usize, f64> {
-    let mut cb: HashMap<NodeIndex, f64> =
-        graph.node_indices().map(|u| (u, 0.0)).collect();
-
-    for s in graph.node_indices() {
-        let mut stack = Vec::new();
-        let mut pred: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
-        let mut sigma: HashMap<NodeIndex, f64> = HashMap::new();
-        let mut dist: HashMap<NodeIndex, i32> = HashMap::new();
-        let mut queue = VecDeque::new();
+    betweenness_centrality_with(graph, &BetweennessOptions::default())
+}
+
+/// Options for [`betweenness_centrality_with`]. The default reproduces
+/// [`betweenness_centrality`]: raw ordered-pair sums over all nodes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BetweennessOptions {
+    /// Divide by the number of ordered pairs that could pass through a
+    /// node: (n−1)(n−2), or n(n−1) with `endpoints`. Scores land in [0, 1]
+    /// and are comparable across graphs.
+    pub normalized: bool,
+    /// Count each unordered pair once (halve). Ignored when `normalized`,
+    /// which already divides by the matching pair count.
+    pub undirected: bool,
+    /// Credit the source and target of every path as lying on it.
+    pub endpoints: bool,
+    /// Only paths starting at these node payloads (`None` = every node).
+    pub sources: Option<Vec<usize>>,
+    /// Only paths ending at these node payloads (`None` = every node).
+    pub targets: Option<Vec<usize>>,
+}
+
+impl BetweennessOptions {
+    /// Factor applied to ordered-pair sums on a graph with `n` nodes.
+    pub fn scale(&self, n: usize) -> f64 {
+        let pairs = if self.endpoints {
+            n * n.saturating_sub(1)
+        } else {
+            n.saturating_sub(1) * n.saturating_sub(2)
+        };
+        match (self.normalized, self.undirected) {
+            (true, _) if pairs > 0 => 1.0 / pairs as f64,
+            (true, _) => 1.0,
+            (false, true) => 0.5,
+            (false, false) => 1.0,
+        }
+    }
+}
+
+/// Betweenness centrality with normalisation, undirected halving, endpoint
+/// credit and source/target subsets.
+///
+/// # Inputs
+/// - `graph`: undirected graph with `usize` node payloads
+/// - `opts`: see [`BetweennessOptions`]; payloads not in the graph are ignored
+///
+/// # Logic
+/// Subset Brandes: BFS only from `sources`, and during accumulation a node
+/// w adds 1 to its predecessors' dependency only if w is a target,
+/// δ_s(v) = Σ_{w: v ∈ P_s(w)} σ_sv/σ_sw · (1[w ∈ T] + δ_s(w)). With
+/// `endpoints`, each reached target w also gets +1 and the source gets +1
+/// per reached target. The ordered-pair sums are finally multiplied by
+/// [`BetweennessOptions::scale`]; halving assumes sources and targets
+/// coincide, so that (s, t) and (t, s) are both counted.
+pub fn betweenness_centrality_with(
+    graph: &Graph<usize, (), Undirected>,
+    opts: &BetweennessOptions,
+) -> HashMap<usize, f64> {
+    let n = graph.node_count();
+    let index: HashMap<usize, usize> = graph.node_indices().map(|u| (graph[u], u.index())).collect();
+    let sources: Vec<usize> = match &opts.sources {
+        Some(subset) => {
+            let mut ids: Vec<usize> = subset.iter().filter_map(|p| index.get(p).copied()).collect();
+            ids.sort_unstable();
+            ids.dedup();
+            ids
+        }
+        None => (0..n).collect(),
+    };
+    let targets: Option<Vec<bool>> = opts.targets.as_ref().map(|subset| {
+        let mut mask = vec![false; n];
+        for p in subset {
+            if let Some(&i) = index.get(p) {
+                mask[i] = true;
+            }
+        }
+        mask
+    });
 
-        // initialize
-        for v in graph.node_indices() {
-            pred.insert(v, Vec::new());
-            sigma.insert(v, 0.0);
-            dist.insert(v, -1);
+    let scale = opts.scale(n);
+    let cb = brandes_scores(graph, &sources, targets.as_deref(), opts.endpoints);
+    graph.node_indices().map(|u| (graph[u], cb[u.index()] * scale)).collect()
+}
+
+/// Sampling scheme for [`betweenness_sampled`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum BetweennessSampling {
+    /// Brandes from `pivots` distinct uniformly drawn sources, scaled by V / pivots.
+    Pivots { pivots: usize, seed: u64 },
+    /// Riondato–Kornaropoulos shortest-path sampling: with probability at
+    /// least 1 − `delta`, every normalised score is within `epsilon`.
+    PathSampling { epsilon: f64, delta: f64, seed: u64 },
+}
+
+/// Sampled betweenness scores on the same scale as [`betweenness_centrality`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BetweennessEstimate {
+    /// node payload → estimated (unnormalised, ordered-pair) betweenness
+    pub scores: HashMap<usize, f64>,
+    pub sampling: BetweennessSampling,
+    /// Pivots or shortest paths actually sampled
+    pub samples: usize,
+    /// Upper bound on the vertex diameter used to size a path sample
+    pub vertex_diameter_bound: Option<usize>,
+}
+
+/// Approximate betweenness centrality by sampling, reproducible via a seed.
+///
+/// # Inputs
+/// - `graph`: undirected graph with `usize` node payloads
+/// - `sampling`: [`BetweennessSampling::Pivots`] or [`BetweennessSampling::PathSampling`]
+///
+/// # Output
+/// - [`BetweennessEstimate`] with the scores and the sample count used
+/// - [`CentralityError::InvalidSampling`] unless 0 < ε < 1 and 0 < δ < 1
+///
+/// # Logic
+/// - Pivots (Brandes–Pich): accumulate dependencies δ_s(v) from k distinct
+///   uniform sources and scale by V/k, an unbiased estimate of Σ_s δ_s(v).
+///   With k ≥ V every node is a source and the result is exact.
+/// - Path sampling (Riondato–Kornaropoulos, 2016): bound the vertex
+///   diameter VD by 2·ecc + 1 from one BFS per component, draw
+///   r = ⌈(0.5/ε²)(⌊log₂(VD − 2)⌋ + 1 + ln(1/δ))⌉ ordered pairs (u, v),
+///   pick one shortest u–v path uniformly by walking back from v with
+///   predecessor probabilities σ_uz/σ_uv, and credit 1/r to every interior
+///   node. The normalised estimate b̃(v) then satisfies |b̃(v) − b(v)| ≤ ε
+///   for all v at once with probability ≥ 1 − δ, where b(v) is betweenness
+///   divided by V(V − 1); it is reported multiplied back by V(V − 1).
+///
+/// Samples are processed in parallel with per-worker BFS buffers.
+pub fn betweenness_sampled(
+    graph: &Graph<usize, (), Undirected>,
+    sampling: BetweennessSampling,
+) -> Result<BetweennessEstimate, CentralityError> {
+    let n = graph.node_count();
+    let (cb, samples, vertex_diameter_bound) = match sampling {
+        BetweennessSampling::Pivots { pivots, seed } => {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let k = pivots.clamp(1, n.max(1));
+            let sources: Vec<usize> =
+                shuffled_nodes(graph, &mut rng).into_iter().take(k).map(|v| v.index()).collect();
+            let scale = n as f64 / k as f64;
+            let cb = brandes_scores(graph, &sources, None, false).into_iter().map(|b| b * scale).collect();
+            (cb, sources.len(), None)
+        }
+        BetweennessSampling::PathSampling { epsilon, delta, seed } => {
+            // false for NaN too, which would otherwise size the sample at usize::MAX
+            let in_unit = |x: f64| x > 0.0 && x < 1.0;
+            if !(in_unit(epsilon) && in_unit(delta)) {
+                return Err(CentralityError::InvalidSampling { epsilon, delta });
+            }
+            let vd = vertex_diameter_bound(graph);
+            let r = riondato_sample_size(vd, epsilon, delta);
+            let cb = path_sampling_scores(graph, r, seed);
+            (cb, r, Some(vd))
+        }
+    };
+    Ok(BetweennessEstimate {
+        scores: graph.node_indices().map(|u| (graph[u], cb[u.index()])).collect(),
+        sampling,
+        samples,
+        vertex_diameter_bound,
+    })
+}
+
+/// Riondato–Kornaropoulos sample size for vertex-diameter bound `vd`.
+fn riondato_sample_size(vd: usize, epsilon: f64, delta: f64) -> usize {
+    let log_vd = (vd.saturating_sub(2).max(1) as f64).log2().floor();
+    ((0.5 / (epsilon * epsilon)) * (log_vd + 1.0 + (1.0 / delta).ln())).ceil() as usize
+}
+
+/// Upper bound on the number of nodes on any shortest path: one BFS per
+/// connected component gives its eccentricity e, and VD ≤ 2e + 1.
+fn vertex_diameter_bound(graph: &Graph<usize, (), Undirected>) -> usize {
+    let mut dist = vec![usize::MAX; graph.node_count()];
+    let mut seen = vec![false; graph.node_count()];
+    let mut queue = VecDeque::new();
+    let mut bound = 0;
+    for start in graph.node_indices() {
+        if seen[start.index()] {
+            continue;
         }
-        sigma.insert(s, 1.0);
-        dist.insert(s, 0);
-        queue.push_back(s);
+        bfs_distances(graph, start, &mut dist, &mut queue);
+        let mut ecc = 0;
+        for (v, &d) in dist.iter().enumerate() {
+            if d != usize::MAX {
+                seen[v] = true;
+                ecc = ecc.max(d);
+            }
+        }
+        bound = bound.max(2 * ecc + 1);
+    }
+    bound
+}
 
-        // BFS phase
-        while let Some(v) = queue.pop_front() {
-            stack.push(v);
-            let dv = dist[&v];
-            for w in graph.neighbors(v) {
-                if dist[&w] < 0 {
-                    dist.insert(w, dv + 1);
-                    queue.push_back(w);
+/// Credit 1/r to the interior nodes of `r` uniformly sampled shortest paths,
+/// then rescale by V(V − 1) to the ordered-pair betweenness scale.
+fn path_sampling_scores(graph: &Graph<usize, (), Undirected>, r: usize, seed: u64) -> Vec<f64> {
+    let n = graph.node_count();
+    if n < 2 || r == 0 {
+        return vec![0.0; n];
+    }
+    // draw every pair and per-sample seed up front so results do not depend
+    // on how rayon schedules the samples
+    let mut rng = StdRng::seed_from_u64(seed);
+    let draws: Vec<(usize, usize, u64)> = (0..r)
+        .map(|_| {
+            let u = rng.random_range(0..n);
+            let mut v = rng.random_range(0..n - 1);
+            if v >= u {
+                v += 1;
+            }
+            (u, v, rng.random())
+        })
+        .collect();
+
+    let adj = Adjacency::new(graph);
+    let hits = draws
+        .par_iter()
+        .fold(
+            || (BrandesState::new(n), vec![0u64; n]),
+            |(mut state, mut hits), &(u, v, path_seed)| {
+                state.bfs(&adj, u, Some(v));
+                if state.dist[v] != usize::MAX {
+                    let mut path_rng = StdRng::seed_from_u64(path_seed);
+                    let mut w = v;
+                    while state.dist[w] > 1 {
+                        // predecessor z chosen with probability σ_uz / σ_uw
+                        let mut x = path_rng.random::<f64>() * state.sigma[w];
+                        let dw = state.dist[w];
+                        let mut pick = None;
+                        for &z in adj.neighbors(w) {
+                            if state.dist[z] != usize::MAX && state.dist[z] + 1 == dw {
+                                pick = Some(z);
+                                x -= state.sigma[z];
+                                if x < 0.0 {
+                                    break;
+                                }
+                            }
+                        }
+                        w = pick.expect("a reached node has a predecessor");
+                        hits[w] += 1;
+                    }
                 }
-                if dist[&w] == dv + 1 {
-                    let sw = sigma[&w];
-                    sigma.insert(w, sw + sigma[&v]);
-                    pred.get_mut(&w).unwrap().push(v);
+                (state, hits)
+            },
+        )
+        .map(|(_, hits)| hits)
+        .reduce(
+            || vec![0u64; n],
+            |mut a, b| {
+                for (x, y) in a.iter_mut().zip(b) {
+                    *x += y;
                 }
+                a
+            },
+        );
+    let scale = (n * (n - 1)) as f64 / r as f64;
+    hits.into_iter().map(|h| h as f64 * scale).collect()
+}
+
+/// Edge betweenness centrality (Brandes’ algorithm, edge variant).
+/// Returns map from `(smaller payload, larger payload)` → betweenness score.
+///
+/// # Logic
+/// The same parallel Brandes pass as [`betweenness_centrality`]; during
+/// accumulation each shortest-path DAG arc (v, w) carries the share
+/// σ_sv/σ_sw · (1 + δ_s(w)) of paths through it, which is credited to its
+/// edge. Scores are unnormalised ordered-pair sums (each unordered pair
+/// counts twice); parallel edges are summed under one key.
+pub fn edge_betweenness_centrality(
+    graph: &Graph<usize, (), Undirected>,
+) -> HashMap<(usize, usize), f64> {
+    let adj = Adjacency::new(graph);
+    let sources: Vec<usize> = (0..graph.node_count()).collect();
+    let scores = edge_brandes_scores(&adj, graph.node_count(), graph.edge_count(), &sources);
+    let mut out = HashMap::with_capacity(graph.edge_count());
+    for e in graph.edge_indices() {
+        let (a, b) = graph.edge_endpoints(e).expect("edge index from the graph");
+        let (a, b) = (graph[a], graph[b]);
+        *out.entry((a.min(b), a.max(b))).or_insert(0.0) += scores[e.index()];
+    }
+    out
+}
+
+/// One step of the Girvan–Newman dendrogram: after `edges_removed` edge
+/// removals, community `parent` split and the nodes in `moved` (the smaller
+/// piece) became community `child`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommunitySplit {
+    /// Edges removed in total when the split happened
+    pub edges_removed: usize,
+    /// Community id that split
+    pub parent: usize,
+    /// Id of the new community formed by `moved`
+    pub child: usize,
+    /// Node payloads moved from `parent` to `child`, sorted
+    pub moved: Vec<usize>,
+    /// Number of communities after the split
+    pub communities: usize,
+    /// Modularity of the partition after the split (on the input graph)
+    pub modularity: f64,
+}
+
+/// Girvan–Newman dendrogram and its maximum-modularity cut.
+#[derive(Debug, Clone, Serialize)]
+pub struct GirvanNewmanResult {
+    /// node payload → community before any removal (connected components)
+    pub initial: HashMap<usize, usize>,
+    /// Modularity of `initial`
+    pub initial_modularity: f64,
+    /// Splits in the order they happened
+    pub splits: Vec<CommunitySplit>,
+    /// Number of splits applied in the best partition
+    pub best_level: usize,
+    /// Modularity of `best_partition`
+    pub best_modularity: f64,
+    /// node payload → community id in the best partition
+    pub best_partition: HashMap<usize, usize>,
+}
+
+impl GirvanNewmanResult {
+    /// Partition after the first `level` splits (node payload → community).
+    pub fn partition_at(&self, level: usize) -> HashMap<usize, usize> {
+        let mut partition = self.initial.clone();
+        for split in self.splits.iter().take(level) {
+            for v in &split.moved {
+                partition.insert(*v, split.child);
+            }
+        }
+        partition
+    }
+}
+
+/// Newman–Girvan modularity of `partition` (node payload → community):
+/// Q = Σ_c [ L_c/m − (d_c/2m)² ], with L_c the edges inside community c and
+/// d_c its total degree. Nodes missing from `partition` form singletons.
+pub fn modularity(graph: &Graph<usize, (), Undirected>, partition: &HashMap<usize, usize>) -> f64 {
+    let m = graph.edge_count() as f64;
+    if m == 0.0 {
+        return 0.0;
+    }
+    let label = |v: NodeIndex| partition.get(&graph[v]).map_or((1, v.index()), |&c| (0, c));
+    let mut inside: HashMap<(u8, usize), f64> = HashMap::new();
+    let mut degree: HashMap<(u8, usize), f64> = HashMap::new();
+    for e in graph.edge_indices() {
+        let (a, b) = graph.edge_endpoints(e).expect("edge index from the graph");
+        let (ca, cb) = (label(a), label(b));
+        *degree.entry(ca).or_insert(0.0) += 1.0;
+        *degree.entry(cb).or_insert(0.0) += 1.0;
+        if ca == cb {
+            *inside.entry(ca).or_insert(0.0) += 1.0;
+        }
+    }
+    let within: f64 = inside.values().sum::<f64>() / m;
+    let expected: f64 = degree.values().map(|d| (d / (2.0 * m)).powi(2)).sum();
+    within - expected
+}
+
+/// Girvan–Newman divisive community detection.
+///
+/// # Inputs
+/// - `graph`: undirected graph with `usize` node payloads
+/// - `max_splits`: stop after this many community splits (`None` = until
+///   every edge is removed)
+///
+/// # Output
+/// - [`GirvanNewmanResult`]: the dendrogram as a list of splits, each with
+///   the modularity of the resulting partition, and the best partition
+///
+/// # Logic
+/// Repeatedly remove the edge of highest edge betweenness (ties, up to a
+/// relative 1e-9, go to the lowest edge index). Removing an edge only
+/// changes betweenness inside its own community, so only that community's
+/// edges are recomputed, with Brandes restricted to its nodes as sources.
+/// When a removal disconnects the community, the smaller piece becomes a
+/// new community and the modularity of the new partition is measured
+/// against the input graph. O(m · n_c · m_c) for community sizes n_c, m_c:
+/// intended for graphs of a few thousand edges or a small `max_splits`.
+/// Self-loops never split a community and are left in place.
+pub fn girvan_newman(
+    graph: &Graph<usize, (), Undirected>,
+    max_splits: Option<usize>,
+) -> GirvanNewmanResult {
+    let n = graph.node_count();
+    let endpoints: Vec<(usize, usize)> = graph
+        .edge_indices()
+        .map(|e| {
+            let (a, b) = graph.edge_endpoints(e).expect("edge index from the graph");
+            (a.index(), b.index())
+        })
+        .collect();
+    let m = endpoints.len();
+    let mut alive: Vec<bool> = endpoints.iter().map(|&(u, v)| u != v).collect();
+    let mut adj = Adjacency::from_edges(n, &endpoints, &alive);
+
+    // connected components as the initial communities
+    let mut community = vec![usize::MAX; n];
+    let mut members: Vec<Vec<usize>> = Vec::new();
+    for start in 0..n {
+        if community[start] == usize::MAX {
+            let piece = reachable(&adj, start);
+            for &v in &piece {
+                community[v] = members.len();
             }
+            members.push(piece);
         }
+    }
+    let payload_partition = |community: &[usize]| -> HashMap<usize, usize> {
+        (0..n).map(|v| (graph[NodeIndex::new(v)], community[v])).collect()
+    };
+    let initial = payload_partition(&community);
+    let initial_modularity = modularity(graph, &initial);
+
+    let all: Vec<usize> = (0..n).collect();
+    let mut score = edge_brandes_scores(&adj, n, m, &all);
+    let mut splits = Vec::new();
+    let mut best = (0, initial_modularity);
+    let mut removed = 0;
+
+    while max_splits.is_none_or(|k| splits.len() < k) {
+        // parallel sums differ in the last bits, so near-ties go to the lowest index
+        let top = (0..m).filter(|&e| alive[e]).map(|e| score[e]).fold(f64::NEG_INFINITY, f64::max);
+        let Some(e) = (0..m).find(|&e| alive[e] && score[e] >= top - 1e-9 * top.abs()) else {
+            break;
+        };
+        alive[e] = false;
+        removed += 1;
+        adj = Adjacency::from_edges(n, &endpoints, &alive);
 
-        // accumulation phase
-        let mut delta: HashMap<NodeIndex, f64> =
-            graph.node_indices().map(|v| (v, 0.0)).collect();
-        while let Some(w) = stack.pop() {
-            let dw = delta[&w];
-            for &v in &pred[&w] {
-                let c = (sigma[&v] / sigma[&w]) * (1.0 + dw);
-                *delta.get_mut(&v).unwrap() += c;
+        let (u, v) = endpoints[e];
+        let parent = community[u];
+        let affected = members[parent].clone();
+        let piece = reachable(&adj, u);
+        if piece.binary_search(&v).is_err() {
+            let rest: Vec<usize> =
+                affected.iter().copied().filter(|x| piece.binary_search(x).is_err()).collect();
+            let (stay, moved_ids) = if piece.len() < rest.len() { (rest, piece) } else { (piece, rest) };
+            let child = members.len();
+            for &x in &moved_ids {
+                community[x] = child;
             }
-            if w != s {
-                *cb.get_mut(&w).unwrap() += delta[&w];
+            let mut moved: Vec<usize> = moved_ids.iter().map(|&x| graph[NodeIndex::new(x)]).collect();
+            moved.sort_unstable();
+            members[parent] = stay;
+            members.push(moved_ids);
+
+            let modularity = modularity(graph, &payload_partition(&community));
+            if modularity > best.1 {
+                best = (splits.len() + 1, modularity);
+            }
+            splits.push(CommunitySplit {
+                edges_removed: removed,
+                parent,
+                child,
+                moved,
+                communities: members.len(),
+                modularity,
+            });
+        }
+
+        // recompute betweenness inside the old community (now one or two)
+        let local = edge_brandes_scores(&adj, n, m, &affected);
+        for &x in &affected {
+            for (_, f) in adj.arcs(x) {
+                score[f] = local[f];
             }
         }
     }
 
-    // map back to payloads
-    cb.into_iter()
-      .map(|(idx, val)| (graph[idx], val))
-      .collect()
+    let mut result = GirvanNewmanResult {
+        initial,
+        initial_modularity,
+        splits,
+        best_level: best.0,
+        best_modularity: best.1,
+        best_partition: HashMap::new(),
+    };
+    result.best_partition = result.partition_at(best.0);
+    result
 }
 
+/// Nodes reachable from `start`, sorted by index.
+fn reachable(adj: &Adjacency, start: usize) -> Vec<usize> {
+    let mut seen = vec![false; adj.offsets.len() - 1];
+    let mut stack = vec![start];
+    seen[start] = true;
+    let mut out = Vec::new();
+    while let Some(v) = stack.pop() {
+        out.push(v);
+        for &w in adj.neighbors(v) {
+            if !seen[w] {
+                seen[w] = true;
+                stack.push(w);
+            }
+        }
+    }
+    out.sort_unstable();
+    out
+}
+
+/// Per-edge Brandes scores over `sources` on `adj` (edge indices < `m`).
+fn edge_brandes_scores(adj: &Adjacency, n: usize, m: usize, sources: &[usize]) -> Vec<f64> {
+    sources
+        .par_iter()
+        .fold(
+            || (BrandesState::new(n), vec![0.0; m]),
+            |(mut state, mut eb), &s| {
+                state.run(adj, s, None);
+                for &(v, w, e) in &state.arcs {
+                    eb[e] += (state.sigma[v] / state.sigma[w]) * (1.0 + state.delta[w]);
+                }
+                (state, eb)
+            },
+        )
+        .map(|(_, eb)| eb)
+        .reduce(
+            || vec![0.0; m],
+            |mut a, b| {
+                for (x, y) in a.iter_mut().zip(b) {
+                    *x += y;
+                }
+                a
+            },
+        )
+}
+
+/// Sum of Brandes dependencies δ_s(v) over `sources`, indexed by node
+/// index; only paths to `targets` (all nodes if `None`) are counted, and
+/// `endpoints` also credits each path's two ends.
+fn brandes_scores(
+    graph: &Graph<usize, (), Undirected>,
+    sources: &[usize],
+    targets: Option<&[bool]>,
+    endpoints: bool,
+) -> Vec<f64> {
+    let n = graph.node_count();
+    let adj = Adjacency::new(graph);
+    let is_target = |w: usize| targets.is_none_or(|t| t[w]);
+    sources
+        .par_iter()
+        .fold(
+            || (BrandesState::new(n), vec![0.0; n]),
+            |(mut state, mut cb), &s| {
+                state.run(&adj, s, targets);
+                for &w in &state.order[1..] {
+                    cb[w] += state.delta[w];
+                }
+                if endpoints {
+                    let reached = state.order[1..].iter().filter(|&&w| is_target(w)).count();
+                    cb[s] += reached as f64;
+                    for &w in state.order[1..].iter().filter(|&&w| is_target(w)) {
+                        cb[w] += 1.0;
+                    }
+                }
+                (state, cb)
+            },
+        )
+        .map(|(_, cb)| cb)
+        .reduce(
+            || vec![0.0; n],
+            |mut a, b| {
+                for (x, y) in a.iter_mut().zip(b) {
+                    *x += y;
+                }
+                a
+            },
+        )
+}
+
+/// Compressed adjacency lists (neighbour multiset of every node, in
+/// petgraph's iteration order, so parallel edges keep their multiplicity),
+/// with the edge index of every entry.
+struct Adjacency {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    edges: Vec<usize>,
+}
+
+impl Adjacency {
+    fn new(graph: &Graph<usize, (), Undirected>) -> Self {
+        let mut offsets = Vec::with_capacity(graph.node_count() + 1);
+        let mut targets = Vec::with_capacity(2 * graph.edge_count());
+        let mut edges = Vec::with_capacity(2 * graph.edge_count());
+        offsets.push(0);
+        for u in graph.node_indices() {
+            for e in graph.edges(u) {
+                targets.push(e.target().index());
+                edges.push(e.id().index());
+            }
+            offsets.push(targets.len());
+        }
+        Adjacency { offsets, targets, edges }
+    }
 
+    /// Adjacency of `n` nodes over the `endpoints[e]` with `alive[e]`,
+    /// keeping `e` as the edge index.
+    fn from_edges(n: usize, endpoints: &[(usize, usize)], alive: &[bool]) -> Self {
+        let mut offsets = vec![0; n + 1];
+        for (&(u, v), _) in endpoints.iter().zip(alive).filter(|&(_, &a)| a) {
+            offsets[u + 1] += 1;
+            offsets[v + 1] += 1;
+        }
+        for i in 0..n {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut fill = offsets.clone();
+        let mut targets = vec![0; offsets[n]];
+        let mut edges = vec![0; offsets[n]];
+        for (e, &(u, v)) in endpoints.iter().enumerate().filter(|&(e, _)| alive[e]) {
+            for (a, b) in [(u, v), (v, u)] {
+                targets[fill[a]] = b;
+                edges[fill[a]] = e;
+                fill[a] += 1;
+            }
+        }
+        Adjacency { offsets, targets, edges }
+    }
+
+    fn neighbors(&self, u: usize) -> &[usize] {
+        &self.targets[self.offsets[u]..self.offsets[u + 1]]
+    }
+
+    /// `(neighbour, edge index)` pairs of `u`.
+    fn arcs(&self, u: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let range = self.offsets[u]..self.offsets[u + 1];
+        self.targets[range.clone()].iter().copied().zip(self.edges[range].iter().copied())
+    }
+}
+
+/// Per-worker buffers for one single-source Brandes pass.
+struct BrandesState {
+    /// BFS distance from the source (`usize::MAX` = unreached)
+    dist: Vec<usize>,
+    /// Number of shortest paths from the source
+    sigma: Vec<f64>,
+    /// Dependency of the source on each node
+    delta: Vec<f64>,
+    /// Nodes reached, in BFS (non-decreasing distance) order
+    order: Vec<usize>,
+    /// Shortest-path DAG arcs (v, w, edge) with dist(w) = dist(v) + 1, in BFS order of v
+    arcs: Vec<(usize, usize, usize)>,
+}
+
+impl BrandesState {
+    fn new(n: usize) -> Self {
+        BrandesState {
+            dist: vec![usize::MAX; n],
+            sigma: vec![0.0; n],
+            delta: vec![0.0; n],
+            order: Vec::with_capacity(n),
+            arcs: Vec::new(),
+        }
+    }
+
+    /// Brandes pass from `s`: [`BrandesState::bfs`], then accumulate
+    /// dependencies over the shortest-path DAG arcs in reverse, counting
+    /// only paths that end at `targets` (all nodes if `None`).
+    fn run(&mut self, adj: &Adjacency, s: usize, targets: Option<&[bool]>) {
+        self.bfs(adj, s, None);
+        // arcs out of deeper nodes were recorded later, so walking them in
+        // reverse finalises delta[w] before it is pushed back to v
+        for &(v, w, _) in self.arcs.iter().rev() {
+            let ends = if targets.is_none_or(|t| t[w]) { 1.0 } else { 0.0 };
+            self.delta[v] += (self.sigma[v] / self.sigma[w]) * (ends + self.delta[w]);
+        }
+    }
+
+    /// BFS from `s` counting shortest paths and recording the DAG arcs.
+    /// With a `target`, stop once every node one hop closer than it has been
+    /// expanded, i.e. as soon as σ(target) is final. Only entries of
+    /// previously reached nodes are reset, so a pass costs
+    /// O(reached nodes + their edges).
+    fn bfs(&mut self, adj: &Adjacency, s: usize, target: Option<usize>) {
+        for &v in &self.order {
+            self.dist[v] = usize::MAX;
+            self.sigma[v] = 0.0;
+            self.delta[v] = 0.0;
+        }
+        self.order.clear();
+        self.arcs.clear();
+
+        self.dist[s] = 0;
+        self.sigma[s] = 1.0;
+        self.order.push(s);
+        let mut head = 0;
+        while head < self.order.len() {
+            let v = self.order[head];
+            head += 1;
+            let dv = self.dist[v];
+            if target.is_some_and(|t| dv >= self.dist[t]) {
+                break;
+            }
+            for (w, e) in adj.arcs(v) {
+                if self.dist[w] == usize::MAX {
+                    self.dist[w] = dv + 1;
+                    self.order.push(w);
+                }
+                if self.dist[w] == dv + 1 {
+                    self.sigma[w] += self.sigma[v];
+                    self.arcs.push((v, w, e));
+                }
+            }
+        }
+    }
+}
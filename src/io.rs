@@ -1,52 +1,508 @@
-//! Module `io`: load Facebook edge list into a `petgraph::Graph`.
+//! Module `io`: load edge lists (SNAP style) and the ego-Facebook tarball
+//! into a `petgraph::Graph`.
 
-use std::{collections::HashMap, error::Error, fs::File};
-use flate2::read::GzDecoder;
-use csv::ReaderBuilder;
-use petgraph::{Graph, Undirected};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    num::ParseIntError,
+    path::{Path, PathBuf},
+};
+use flate2::read::MultiGzDecoder;
+use petgraph::{Graph, Undirected, prelude::NodeIndex};
+
+/// First two bytes of every gzip member (RFC 1952).
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Path reported by [`read_edge_list`], which has no file name to report.
+const STREAM_PATH: &str = "<stream>";
+
+/// Everything that can go wrong while loading a graph.
+///
+/// Every variant carries the `path` it was loading; row-level variants also
+/// carry the 1-based `line` number and the raw `text` of that line. For
+/// files inside the ego-Facebook archive, `path` is `<archive>/<member>`.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be opened (missing, permissions, …).
+    Open { path: PathBuf, source: io::Error },
+    /// Reading failed at `line`: an I/O error on a plain-text stream, or
+    /// invalid UTF-8 in any stream (including the output of an intact gzip one).
+    Read { path: PathBuf, line: usize, source: io::Error },
+    /// The gzip stream is corrupt or truncated; detected while reading `line`.
+    Gzip { path: PathBuf, line: usize, source: io::Error },
+    /// The tar archive itself could not be read.
+    Archive { path: PathBuf, source: io::Error },
+    /// A row has fewer columns than required (e.g. an edge with one endpoint).
+    MissingField { path: PathBuf, line: usize, text: String },
+    /// A node ID is not a non-negative integer.
+    InvalidId {
+        path: PathBuf,
+        line: usize,
+        text: String,
+        token: String,
+        source: ParseIntError,
+    },
+    /// Any other malformed row: feature bits, feature names, …
+    Malformed { path: PathBuf, line: usize, text: String, reason: String },
+    /// A per-ego file (`<ego>.<extension>`) is absent from the archive.
+    MissingEgoFile { path: PathBuf, ego: usize, extension: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Open { path, source } => {
+                write!(f, "{}: cannot open: {}", path.display(), source)
+            }
+            LoadError::Read { path, line, source } => {
+                write!(f, "{}:{}: read error: {}", path.display(), line, source)
+            }
+            LoadError::Gzip { path, line, source } => {
+                write!(f, "{}:{}: corrupt gzip stream: {}", path.display(), line, source)
+            }
+            LoadError::Archive { path, source } => {
+                write!(f, "{}: unreadable tar archive: {}", path.display(), source)
+            }
+            LoadError::MissingField { path, line, text } => {
+                write!(f, "{}:{}: expected two node IDs, got {:?}", path.display(), line, text)
+            }
+            LoadError::InvalidId { path, line, text, token, source } => write!(
+                f,
+                "{}:{}: invalid node ID {:?} in {:?}: {}",
+                path.display(), line, token, text, source
+            ),
+            LoadError::Malformed { path, line, text, reason } => {
+                write!(f, "{}:{}: {} in {:?}", path.display(), line, reason, text)
+            }
+            LoadError::MissingEgoFile { path, ego, extension } => {
+                write!(f, "{}: missing {}.{}", path.display(), ego, extension)
+            }
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Open { source, .. }
+            | LoadError::Read { source, .. }
+            | LoadError::Gzip { source, .. }
+            | LoadError::Archive { source, .. } => Some(source),
+            LoadError::InvalidId { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 /// Load gzipped space-delimited edge list into an undirected graph.
 ///
+/// Kept for the original Facebook dataset; this is now a thin wrapper
+/// around [`load_edge_list`], which also accepts plain text, comments and tabs.
+///
 /// # Inputs
 /// - `path`: file path to gzipped edge list (u v per line)
 ///
 /// # Outputs
 /// - `Ok(Graph<usize, (), Undirected>)`: nodes payload = original ID
-/// - `Err(...)` on I/O or parse errors
-///
-/// # High-level logic
-/// 1. Open & decompress via `GzDecoder`.  
-/// 2. Use CSV reader with space delimiter, no headers.  
-/// 3. Map each original node ID → unique `NodeIndex`.  
-/// 4. Insert edge for each `(u,v)`.
+/// - `Err(LoadError)` on I/O or parse errors
 pub fn load_facebook_graph(
     path: &str
-) -> Result<Graph<usize, (), Undirected>, Box<dyn Error>> {
-    let file = File::open(path)?;                 // open compressed file
-    let decoder = GzDecoder::new(file);           // wrap in gzip decoder
+) -> Result<Graph<usize, (), Undirected>, LoadError> {
+    load_edge_list(path)
+}
 
-    let mut reader = ReaderBuilder::new()
-        .has_headers(false)
-        .delimiter(b' ')
-        .from_reader(decoder);
+/// Load a whitespace-delimited edge list, plain or gzip-compressed.
+///
+/// # Inputs
+/// - `path`: edge-list file; compression is detected from the gzip magic
+///   bytes, not the file extension
+///
+/// # Outputs
+/// - `Ok(Graph<usize, (), Undirected>)`: nodes payload = original ID
+/// - `Err(LoadError)` on I/O errors, or on parse errors with the 1-based line number
+///
+/// # High-level logic
+/// 1. Peek at the first bytes; wrap in `MultiGzDecoder` if they are `1f 8b`.
+/// 2. Parse the buffered stream as in [`read_edge_list`].
+pub fn load_edge_list(
+    path: impl AsRef<Path>,
+) -> Result<Graph<usize, (), Undirected>, LoadError> {
+    let path = path.as_ref();
+    let (reader, is_gzip) = open_maybe_gzip(path)?;
+    parse_edge_list(reader, path, is_gzip)
+}
 
-    let mut graph = Graph::<usize, (), Undirected>::new_undirected();
-    let mut node_map: HashMap<usize, _> = HashMap::new();
+/// Parse an edge list from any buffered reader.
+///
+/// Errors report the path as `<stream>`.
+///
+/// # Format
+/// - one edge `u v` per line, separated by any run of spaces / tabs
+/// - columns after the second (weights, timestamps, …) are ignored
+/// - blank lines and lines starting with `#` or `%` are skipped
+///
+/// # High-level logic
+/// 1. Map each original node ID → unique `NodeIndex`.
+/// 2. Insert an edge for each `(u,v)`; duplicates are kept as parallel edges.
+pub fn read_edge_list<R: BufRead>(
+    reader: R,
+) -> Result<Graph<usize, (), Undirected>, LoadError> {
+    parse_edge_list(reader, Path::new(STREAM_PATH), false)
+}
 
-    for record in reader.records() {
-        let rec = record?;
-        let u: usize = rec[0].parse()?;
-        let v: usize = rec[1].parse()?;
+fn parse_edge_list<R: BufRead>(
+    reader: R,
+    path: &Path,
+    is_gzip: bool,
+) -> Result<Graph<usize, (), Undirected>, LoadError> {
+    let mut builder = GraphBuilder::default();
 
-        // Map or insert u
-        let i = *node_map.entry(u)
-            .or_insert_with(|| graph.add_node(u));
-        // Map or insert v
-        let j = *node_map.entry(v)
-            .or_insert_with(|| graph.add_node(v));
+    // split into raw lines first, so that only errors from the reader itself
+    // (the gzip decoder, when there is one) are reported as `Gzip`, and
+    // invalid UTF-8 inside an intact stream is a `Read` error
+    for (i, bytes) in reader.split(b'\n').enumerate() {
+        let line_no = i + 1;
+        let mut bytes = bytes.map_err(|source| {
+            let path = path.to_path_buf();
+            if is_gzip {
+                LoadError::Gzip { path, line: line_no, source }
+            } else {
+                LoadError::Read { path, line: line_no, source }
+            }
+        })?;
+        if bytes.last() == Some(&b'\r') {
+            bytes.pop();
+        }
+        let line = String::from_utf8(bytes).map_err(|e| LoadError::Read {
+            path: path.to_path_buf(),
+            line: line_no,
+            source: io::Error::new(io::ErrorKind::InvalidData, e),
+        })?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('%') {
+            continue;
+        }
 
-        graph.add_edge(i, j, ());                // add undirected edge
+        let mut fields = trimmed.split_whitespace();
+        let (Some(a), Some(b)) = (fields.next(), fields.next()) else {
+            return Err(LoadError::MissingField {
+                path: path.to_path_buf(),
+                line: line_no,
+                text: line,
+            });
+        };
+        let u = parse_id(path, line_no, &line, a)?;
+        let v = parse_id(path, line_no, &line, b)?;
+
+        builder.add_edge(u, v);
     }
 
-    Ok(graph)
+    Ok(builder.graph)
+}
+
+/// A ground-truth social circle inside one ego network.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle {
+    /// Circle label from the `.circles` file, e.g. `circle0`
+    pub name: String,
+    /// Node payloads (original IDs) in the circle
+    pub members: Vec<usize>,
+}
+
+/// One ego network from the ego-Facebook tarball.
+///
+/// Feature names are local to each ego network (the anonymised feature
+/// spaces differ between egos), so vectors are only comparable within one
+/// `EgoNetwork`.
+#[derive(Debug, Clone)]
+pub struct EgoNetwork {
+    /// Node payload of the ego
+    pub ego: usize,
+    /// Friends of the ego (every node listed in `<ego>.feat`), sorted
+    pub alters: Vec<usize>,
+    /// Feature names from `<ego>.featnames`, indexed by feature position
+    pub feature_names: Vec<String>,
+    /// Binary feature vector per node (ego and alters)
+    pub features: HashMap<usize, Vec<bool>>,
+    /// Ground-truth circles from `<ego>.circles`
+    pub circles: Vec<Circle>,
+}
+
+impl EgoNetwork {
+    /// Names of the features set for `node`, or `None` if `node` is not in
+    /// this ego network.
+    pub fn active_features(&self, node: usize) -> Option<Vec<&str>> {
+        let vector = self.features.get(&node)?;
+        Some(
+            vector
+                .iter()
+                .zip(&self.feature_names)
+                .filter(|&(&on, _)| on)
+                .map(|(_, name)| name.as_str())
+                .collect(),
+        )
+    }
+}
+
+/// The full ego-Facebook dataset: combined graph plus per-ego attributes.
+#[derive(Debug, Clone)]
+pub struct EgoFacebook {
+    /// Union of all ego networks; nodes payload = original ID, no duplicate edges
+    pub graph: Graph<usize, (), Undirected>,
+    /// Ego networks sorted by ego ID
+    pub egos: Vec<EgoNetwork>,
+}
+
+impl EgoFacebook {
+    /// IDs of the egos whose network contains `node` (as ego or alter).
+    pub fn memberships(&self, node: usize) -> Vec<usize> {
+        self.egos
+            .iter()
+            .filter(|e| e.ego == node || e.alters.binary_search(&node).is_ok())
+            .map(|e| e.ego)
+            .collect()
+    }
+}
+
+/// Load the SNAP ego-Facebook tarball (`facebook.tar.gz`).
+///
+/// # Inputs
+/// - `path`: tar archive, gzip-compressed or not, with per-ego
+///   `.edges`, `.circles`, `.feat`, `.egofeat` and `.featnames` files
+///
+/// # Outputs
+/// - `Ok(EgoFacebook)`: combined graph + one [`EgoNetwork`] per ego
+/// - `Err(LoadError)` on I/O errors, missing per-ego files, or parse errors
+///   (reported against `<archive>/<ego>.<ext>`)
+///
+/// # High-level logic
+/// 1. Read every archive entry into memory, grouped by ego ID and extension.
+/// 2. Per ego: parse feature names, ego / alter feature vectors and circles.
+/// 3. Build the graph from all `.edges` files plus an ego–alter edge for
+///    each alter, skipping edges already present (ego networks overlap).
+pub fn load_ego_facebook(path: impl AsRef<Path>) -> Result<EgoFacebook, LoadError> {
+    let archive_path = path.as_ref();
+    let (reader, _) = open_maybe_gzip(archive_path)?;
+    let archive_err = |source| LoadError::Archive { path: archive_path.to_path_buf(), source };
+
+    let mut archive = tar::Archive::new(reader);
+    let mut files: BTreeMap<usize, HashMap<String, String>> = BTreeMap::new();
+
+    for entry in archive.entries().map_err(archive_err)? {
+        let mut entry = entry.map_err(archive_err)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let entry_path = entry.path().map_err(archive_err)?.into_owned();
+        let (Some(stem), Some(ext)) = (
+            entry_path.file_stem().and_then(|s| s.to_str()),
+            entry_path.extension().and_then(|s| s.to_str()),
+        ) else {
+            continue;
+        };
+        let Ok(ego) = stem.parse::<usize>() else {
+            continue;
+        };
+        let ext = ext.to_string();
+        let mut text = String::new();
+        entry.read_to_string(&mut text).map_err(archive_err)?;
+        files.entry(ego).or_default().insert(ext, text);
+    }
+
+    let mut builder = GraphBuilder::default();
+    let mut seen: HashSet<(usize, usize)> = HashSet::new();
+    let mut add_unique = |builder: &mut GraphBuilder, u: usize, v: usize| {
+        if seen.insert((u.min(v), u.max(v))) {
+            builder.add_edge(u, v);
+        }
+    };
+
+    let mut egos = Vec::with_capacity(files.len());
+    for (ego, parts) in &files {
+        let ego = *ego;
+        let get = |ext: &str| {
+            let text = parts.get(ext).map(String::as_str).ok_or_else(|| {
+                LoadError::MissingEgoFile {
+                    path: archive_path.to_path_buf(),
+                    ego,
+                    extension: ext.to_string(),
+                }
+            })?;
+            Ok::<_, LoadError>((archive_path.join(format!("{}.{}", ego, ext)), text))
+        };
+
+        let (featnames_path, featnames) = get("featnames")?;
+        let feature_names = parse_featnames(&featnames_path, featnames)?;
+        let n_feat = feature_names.len();
+
+        let mut features = HashMap::new();
+        let (egofeat_path, egofeat) = get("egofeat")?;
+        let ego_vector = parse_bits(&egofeat_path, 1, egofeat.trim(), egofeat.split_whitespace(), n_feat)?;
+        features.insert(ego, ego_vector);
+
+        let (feat_path, feat) = get("feat")?;
+        let mut alters = Vec::new();
+        for (line_no, line) in numbered_lines(feat) {
+            let mut fields = line.split_whitespace();
+            let node = parse_id(&feat_path, line_no, line, fields.next().unwrap_or(""))?;
+            let vector = parse_bits(&feat_path, line_no, line, fields, n_feat)?;
+            features.insert(node, vector);
+            alters.push(node);
+        }
+        alters.sort_unstable();
+
+        let (edges_path, edges) = get("edges")?;
+        for (line_no, line) in numbered_lines(edges) {
+            let mut fields = line.split_whitespace();
+            let (Some(a), Some(b)) = (fields.next(), fields.next()) else {
+                return Err(LoadError::MissingField {
+                    path: edges_path,
+                    line: line_no,
+                    text: line.to_string(),
+                });
+            };
+            let u = parse_id(&edges_path, line_no, line, a)?;
+            let v = parse_id(&edges_path, line_no, line, b)?;
+            add_unique(&mut builder, u, v);
+        }
+        builder.node(ego);
+        for &alter in &alters {
+            add_unique(&mut builder, ego, alter);
+        }
+
+        let (circles_path, circles_text) = get("circles")?;
+        let mut circles = Vec::new();
+        for (line_no, line) in numbered_lines(circles_text) {
+            let mut fields = line.split_whitespace();
+            let name = fields.next().unwrap_or("").to_string();
+            let members = fields
+                .map(|tok| parse_id(&circles_path, line_no, line, tok))
+                .collect::<Result<Vec<_>, _>>()?;
+            circles.push(Circle { name, members });
+        }
+
+        egos.push(EgoNetwork { ego, alters, feature_names, features, circles });
+    }
+
+    Ok(EgoFacebook { graph: builder.graph, egos })
+}
+
+/// Open `path`, transparently decompressing it if it starts with the gzip
+/// magic; the flag reports whether it did.
+fn open_maybe_gzip(path: &Path) -> Result<(Box<dyn BufRead>, bool), LoadError> {
+    let open_err = |source| LoadError::Open { path: path.to_path_buf(), source };
+    let mut file = BufReader::new(File::open(path).map_err(open_err)?);
+    let is_gzip = file.fill_buf().map_err(open_err)?.starts_with(&GZIP_MAGIC);
+
+    if is_gzip {
+        Ok((Box::new(BufReader::new(MultiGzDecoder::new(file))), true))
+    } else {
+        Ok((Box::new(file), false))
+    }
+}
+
+/// Incrementally builds a graph, mapping original IDs → `NodeIndex`.
+#[derive(Default)]
+struct GraphBuilder {
+    graph: Graph<usize, (), Undirected>,
+    node_map: HashMap<usize, NodeIndex>,
+}
+
+impl GraphBuilder {
+    /// Map or insert node `id`.
+    fn node(&mut self, id: usize) -> NodeIndex {
+        let graph = &mut self.graph;
+        *self.node_map.entry(id).or_insert_with(|| graph.add_node(id))
+    }
+
+    /// Insert undirected edge `id_u`–`id_v`, adding endpoints as needed.
+    fn add_edge(&mut self, id_u: usize, id_v: usize) {
+        let i = self.node(id_u);
+        let j = self.node(id_v);
+        self.graph.add_edge(i, j, ());
+    }
+}
+
+/// Non-blank lines with their 1-based line numbers.
+fn numbered_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty())
+}
+
+/// Parse node ID `tok` found on `line_no` (raw `text`) of `path`.
+fn parse_id(path: &Path, line_no: usize, text: &str, tok: &str) -> Result<usize, LoadError> {
+    if tok.is_empty() {
+        return Err(LoadError::MissingField {
+            path: path.to_path_buf(),
+            line: line_no,
+            text: text.to_string(),
+        });
+    }
+    tok.parse().map_err(|source| LoadError::InvalidId {
+        path: path.to_path_buf(),
+        line: line_no,
+        text: text.to_string(),
+        token: tok.to_string(),
+        source,
+    })
+}
+
+/// Parse a whitespace-separated 0/1 vector of exactly `len` entries.
+fn parse_bits<'a>(
+    path: &Path,
+    line_no: usize,
+    text: &str,
+    tokens: impl Iterator<Item = &'a str>,
+    len: usize,
+) -> Result<Vec<bool>, LoadError> {
+    let malformed = |reason: String| LoadError::Malformed {
+        path: path.to_path_buf(),
+        line: line_no,
+        text: text.to_string(),
+        reason,
+    };
+    let bits = tokens
+        .map(|tok| match tok {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            _ => Err(malformed(format!("expected 0 or 1, got {:?}", tok))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if bits.len() != len {
+        return Err(malformed(format!("expected {} features, got {}", len, bits.len())));
+    }
+    Ok(bits)
+}
+
+/// Parse `<index> <name>` lines; names keep their internal spaces.
+fn parse_featnames(path: &Path, text: &str) -> Result<Vec<String>, LoadError> {
+    let mut names = Vec::new();
+    for (line_no, line) in numbered_lines(text) {
+        let malformed = |reason: String| LoadError::Malformed {
+            path: path.to_path_buf(),
+            line: line_no,
+            text: line.to_string(),
+            reason,
+        };
+        let (idx, name) = line
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| malformed("expected `<index> <name>`".to_string()))?;
+        let idx: usize = idx
+            .parse()
+            .map_err(|e| malformed(format!("invalid feature index {:?}: {}", idx, e)))?;
+        if idx != names.len() {
+            return Err(malformed(format!(
+                "feature index {} out of order (expected {})",
+                idx, names.len()
+            )));
+        }
+        names.push(name.to_string());
+    }
+    Ok(names)
 }
@@ -1,79 +1,1242 @@
-//! CLI entrypoint: loads Facebook graph, runs analyses, and prints results.
+//! CLI entrypoint: loads an edge-list graph, runs the requested analyses,
+//! and writes the results to stdout or a report file.
+//!
+//! ```text
+//! ds210 <load|stats|densest|cores|clustering|distribution|closeness|betweenness|
+//!        pagerank|spectral|communities|all> <PATH> [-o FILE] [-d DIR] [-k TOP]
+//!       [--sample N] [--tolerance T] [--seed S]
+//!       [--k-min K] [--continuous] [--gof N] [--compare]
+//!       [--closeness reachable|wasserman-faust|harmonic]
+//!       [--pivots K | --epsilon E [--delta D]] [--normalized] [--undirected] [--endpoints]
+//!       [--damping D] [--personalize IDS] [--katz-alpha A] [--katz-beta B]
+//! ds210 egos <facebook.tar.gz> [-o FILE] [-d DIR]
+//! ```
+//!
+//! With `--out-dir`, every analysis also writes JSON/CSV files and a
+//! `manifest.json` (see `ds210_project::output`).
 
-mod io;
-mod graph_analysis;
-mod stats;
-mod utils;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
 
-use crate::utils::{print_section, measure_time};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ds210_project::output::{self, ClusteringReport, ComparisonReport, CoreReport, EgoSummaryRow, EigenvectorReport, GirvanNewmanReport, KatzReport, PageRankReport, RankedNode, SampledBetweennessReport, TrussReport, ExactDensestReport, NodeRow, PathLengthReport, PowerLawReport, RunWriter};
+use ds210_project::utils::{measure_time, time_it, write_section};
+use ds210_project::graph_analysis::{self, BetweennessOptions, BetweennessSampling, ClosenessVariant, ConvergenceReport, KatzOptions, PageRankOptions, PowerIterationOptions, EstimateOptions, PathSampling};
+use ds210_project::{io as graph_io, stats};
+use ds210_project::stats::{Alternative, GoodnessOfFitOptions, Histogram, PowerLawFit, PowerLawMethod};
 use itertools::Itertools; // for sorted_by_key
+use petgraph::{Graph, Undirected};
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // -- Load the graph once from gzipped edge list
-    print_section("Loading Graph");
-    let graph = io::load_facebook_graph("data/facebook_combined.txt.gz")?;
-    println!(
+/// Analyse cohesion, centrality and degree tails of an undirected graph.
+#[derive(Parser)]
+#[command(name = "ds210", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Load the graph and report its size
+    Load(InputArgs),
+    /// Average shortest-path length: exact (diameter, radius) or sampled with CI
+    Stats(StatsArgs),
+    /// Densest subgraph: 2-approx peeling, optionally Greedy++ refinement and the exact optimum
+    Densest(DensestArgs),
+    /// k-core and k-truss decompositions, compared against the densest subgraph
+    Cores(InputArgs),
+    /// Triangle counts, local/average clustering, transitivity and C(k)
+    Clustering(InputArgs),
+    /// 1-hop / 2-hop distributions and power-law fit
+    Distribution(DistributionArgs),
+    /// Closeness centrality ranking (one BFS per node)
+    Closeness(ClosenessArgs),
+    /// Betweenness centrality ranking: exact Brandes or sampled
+    Betweenness(BetweennessArgs),
+    /// PageRank and personalized PageRank rankings
+    Pagerank(PageRankArgs),
+    /// Eigenvector and Katz centrality rankings and their overlap
+    Spectral(SpectralArgs),
+    /// Edge betweenness and Girvan–Newman communities (slow on large graphs)
+    Communities(CommunityArgs),
+    /// Run every analysis in sequence
+    All(AllArgs),
+    /// Summarise the ego-Facebook tarball (ego networks, circles, features)
+    Egos(InputArgs),
+}
+
+/// Arguments shared by every subcommand.
+#[derive(Args)]
+struct InputArgs {
+    /// Edge list, plain or gzip (`u v` per line, `#`/`%` comments allowed)
+    path: String,
+    /// Write the report to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Also write JSON/CSV results and a manifest into this directory
+    #[arg(short = 'd', long)]
+    out_dir: Option<String>,
+}
+
+/// Shortest-path options.
+#[derive(Args)]
+struct PathArgs {
+    /// Estimate from this many uniformly sampled sources instead of exact BFS
+    #[arg(long)]
+    sample: Option<usize>,
+    /// Keep sampling until the CI half-width is at most this (adaptive mode)
+    #[arg(long)]
+    tolerance: Option<f64>,
+    /// Confidence level of the bootstrap interval
+    #[arg(long, default_value_t = 0.95)]
+    confidence: f64,
+    /// Number of bootstrap replicates
+    #[arg(long, default_value_t = 1000)]
+    bootstrap: usize,
+}
+
+impl PathArgs {
+    /// Estimator options, or `None` for the exact all-pairs computation.
+    fn estimate_options(&self, seed: u64) -> Option<EstimateOptions> {
+        if self.sample.is_none() && self.tolerance.is_none() {
+            return None;
+        }
+        let defaults = EstimateOptions::default();
+        Some(EstimateOptions {
+            sample_size: self.sample.unwrap_or(defaults.sample_size),
+            seed,
+            confidence: self.confidence,
+            bootstrap_replicates: self.bootstrap,
+        })
+    }
+}
+
+/// Closeness formula (see `graph_analysis::ClosenessVariant`).
+#[derive(Clone, Copy, ValueEnum)]
+enum ClosenessArg {
+    /// (N−1) / Σd over reachable nodes (original definition)
+    Reachable,
+    /// Wasserman–Faust: scaled by the fraction of nodes reached
+    WassermanFaust,
+    /// Harmonic centrality: Σ 1/d
+    Harmonic,
+}
+
+impl From<ClosenessArg> for ClosenessVariant {
+    fn from(arg: ClosenessArg) -> Self {
+        match arg {
+            ClosenessArg::Reachable => ClosenessVariant::Reachable,
+            ClosenessArg::WassermanFaust => ClosenessVariant::WassermanFaust,
+            ClosenessArg::Harmonic => ClosenessVariant::Harmonic,
+        }
+    }
+}
+
+/// Closeness options.
+#[derive(Args)]
+struct ClosenessOpts {
+    /// Closeness variant; use wasserman-faust or harmonic on disconnected graphs
+    #[arg(long, value_enum, default_value_t = ClosenessArg::Reachable)]
+    closeness: ClosenessArg,
+}
+
+/// PageRank options.
+#[derive(Args)]
+struct PageRankOpts {
+    /// PageRank damping factor
+    #[arg(long, default_value_t = 0.85)]
+    damping: f64,
+    /// Also run personalized PageRank teleporting to these node IDs (comma-separated)
+    #[arg(long, value_delimiter = ',', value_name = "NODES")]
+    personalize: Vec<usize>,
+}
+
+impl PageRankOpts {
+    fn options(&self) -> PageRankOptions {
+        PageRankOptions { damping: self.damping, ..PageRankOptions::default() }
+    }
+}
+
+/// Katz centrality options (eigenvector centrality has none).
+#[derive(Args)]
+struct SpectralOpts {
+    /// Katz attenuation factor; must be below 1/λ_max (default 0.9/λ_max)
+    #[arg(long)]
+    katz_alpha: Option<f64>,
+    /// Katz constant weight per node
+    #[arg(long, default_value_t = 1.0)]
+    katz_beta: f64,
+}
+
+impl SpectralOpts {
+    fn katz_options(&self) -> KatzOptions {
+        KatzOptions { alpha: self.katz_alpha, beta: self.katz_beta, ..KatzOptions::default() }
+    }
+}
+
+/// Betweenness sampling options (exact Brandes when neither is given).
+#[derive(Args)]
+struct BetweennessOpts {
+    /// Estimate betweenness from this many uniformly sampled pivot sources
+    #[arg(long, conflicts_with = "epsilon")]
+    pivots: Option<usize>,
+    /// Estimate betweenness by shortest-path sampling with this additive error
+    /// on the normalised scale (Riondato–Kornaropoulos)
+    #[arg(long, value_parser = open_unit_interval)]
+    epsilon: Option<f64>,
+    /// Failure probability of the --epsilon guarantee
+    #[arg(long, default_value_t = 0.1, value_parser = open_unit_interval, requires = "epsilon")]
+    delta: f64,
+    /// Normalise betweenness by the number of pairs, (n−1)(n−2)
+    #[arg(long)]
+    normalized: bool,
+    /// Count each unordered pair once instead of twice
+    #[arg(long)]
+    undirected: bool,
+    /// Credit path endpoints too (exact betweenness only)
+    #[arg(long, conflicts_with_all = ["pivots", "epsilon"])]
+    endpoints: bool,
+}
+
+impl BetweennessOpts {
+    /// Normalisation / halving / endpoint options (all nodes as sources and targets).
+    fn options(&self) -> BetweennessOptions {
+        BetweennessOptions {
+            normalized: self.normalized,
+            undirected: self.undirected,
+            endpoints: self.endpoints,
+            ..BetweennessOptions::default()
+        }
+    }
+
+    /// Sampling scheme, or `None` for exact Brandes.
+    fn sampling(&self, seed: u64) -> Option<BetweennessSampling> {
+        match (self.pivots, self.epsilon) {
+            (Some(pivots), _) => Some(BetweennessSampling::Pivots { pivots, seed }),
+            (None, Some(epsilon)) => Some(BetweennessSampling::PathSampling {
+                epsilon,
+                delta: self.delta,
+                seed,
+            }),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Parses a number strictly between 0 and 1.
+fn open_unit_interval(s: &str) -> std::result::Result<f64, String> {
+    let x: f64 = s.parse().map_err(|e| format!("{}", e))?;
+    if x > 0.0 && x < 1.0 { Ok(x) } else { Err(format!("{} is not strictly between 0 and 1", s)) }
+}
+
+/// RNG seed shared by every sampled analysis.
+#[derive(Args)]
+struct SeedArgs {
+    /// RNG seed for sampling and bootstrap
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+}
+
+/// Densest-subgraph options.
+#[derive(Args)]
+struct DensestOpts {
+    /// Also solve exactly (Goldberg max-flow) and report the approximation gap
+    #[arg(long)]
+    exact: bool,
+    /// Also run this many Greedy++ passes (iterative load-weighted peeling)
+    #[arg(long, value_name = "PASSES")]
+    greedy_pp: Option<usize>,
+}
+
+/// Power-law fit options.
+#[derive(Args)]
+struct FitArgs {
+    /// Smallest degree included in the power-law fit (default: chosen by
+    /// minimizing the Kolmogorov–Smirnov distance)
+    #[arg(long)]
+    k_min: Option<usize>,
+    /// Use the closed-form continuous approximation instead of the discrete MLE
+    #[arg(long)]
+    continuous: bool,
+    /// Bootstrap goodness-of-fit test with this many synthetic datasets (0 = skip)
+    #[arg(long, default_value_t = 0)]
+    gof: usize,
+    /// Compare the power law with lognormal, exponential, stretched
+    /// exponential, Poisson and cutoff fits on the 1-hop and 2-hop tails
+    #[arg(long)]
+    compare: bool,
+}
+
+impl FitArgs {
+    fn method(&self) -> PowerLawMethod {
+        if self.continuous { PowerLawMethod::ContinuousApprox } else { PowerLawMethod::Discrete }
+    }
+}
+
+/// Ranking options.
+#[derive(Args)]
+struct RankArgs {
+    /// Number of top-ranked nodes to list
+    #[arg(short = 'k', long, default_value_t = 10)]
+    top: usize,
+}
+
+#[derive(Args)]
+struct StatsArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    paths: PathArgs,
+    #[command(flatten)]
+    seed: SeedArgs,
+}
+
+#[derive(Args)]
+struct DensestArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    densest: DensestOpts,
+}
+
+#[derive(Args)]
+struct DistributionArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    fit: FitArgs,
+    #[command(flatten)]
+    seed: SeedArgs,
+}
+
+#[derive(Args)]
+struct ClosenessArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    rank: RankArgs,
+    #[command(flatten)]
+    closeness: ClosenessOpts,
+}
+
+#[derive(Args)]
+struct BetweennessArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    rank: RankArgs,
+    #[command(flatten)]
+    betweenness: BetweennessOpts,
+    #[command(flatten)]
+    seed: SeedArgs,
+}
+
+#[derive(Args)]
+struct PageRankArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    rank: RankArgs,
+    #[command(flatten)]
+    pagerank: PageRankOpts,
+}
+
+#[derive(Args)]
+struct SpectralArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    rank: RankArgs,
+    #[command(flatten)]
+    spectral: SpectralOpts,
+}
+
+#[derive(Args)]
+struct CommunityArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    rank: RankArgs,
+    /// Stop Girvan–Newman after this many community splits
+    #[arg(long)]
+    splits: Option<usize>,
+}
+
+#[derive(Args)]
+struct AllArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    paths: PathArgs,
+    #[command(flatten)]
+    densest: DensestOpts,
+    #[command(flatten)]
+    fit: FitArgs,
+    #[command(flatten)]
+    rank: RankArgs,
+    #[command(flatten)]
+    closeness: ClosenessOpts,
+    #[command(flatten)]
+    pagerank: PageRankOpts,
+    #[command(flatten)]
+    spectral: SpectralOpts,
+    #[command(flatten)]
+    betweenness: BetweennessOpts,
+    #[command(flatten)]
+    seed: SeedArgs,
+}
+
+type UGraph = Graph<usize, (), Undirected>;
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let input = match &cli.command {
+        Command::Load(a) | Command::Cores(a) | Command::Clustering(a) | Command::Egos(a) => a,
+        Command::Stats(a) => &a.input,
+        Command::Densest(a) => &a.input,
+        Command::Distribution(a) => &a.input,
+        Command::Closeness(a) => &a.input,
+        Command::Betweenness(a) => &a.input,
+        Command::Pagerank(a) => &a.input,
+        Command::Spectral(a) => &a.input,
+        Command::Communities(a) => &a.input,
+        Command::All(a) => &a.input,
+    };
+    let mut out: Box<dyn Write> = match &input.output {
+        Some(file) => Box::new(BufWriter::new(File::create(file)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    if let Command::Egos(_) = &cli.command {
+        return run_egos(&mut out, input);
+    }
+
+    let graph = run_load(&mut out, &input.path)?;
+    let mut export = match &input.out_dir {
+        Some(dir) => Some(RunWriter::create(
+            dir,
+            &input.path,
+            graph.node_count(),
+            graph.edge_count(),
+        )?),
+        None => None,
+    };
+
+    match &cli.command {
+        Command::Load(_) | Command::Egos(_) => {}
+        Command::Stats(a) => run_stats(&mut out, &mut export, &graph, &a.paths, a.seed.seed)?,
+        Command::Densest(a) => run_densest(&mut out, &mut export, &graph, &a.densest)?,
+        Command::Cores(_) => run_cores(&mut out, &mut export, &graph)?,
+        Command::Clustering(_) => run_clustering(&mut out, &mut export, &graph)?,
+        Command::Distribution(a) => run_distribution(&mut out, &mut export, &graph, &a.fit, a.seed.seed)?,
+        Command::Closeness(a) => {
+            let variant = a.closeness.closeness.into();
+            run_closeness(&mut out, &mut export, &graph, &a.rank, variant)?;
+        }
+        Command::Betweenness(a) => {
+            run_betweenness(&mut out, &mut export, &graph, &a.rank, &a.betweenness, a.seed.seed)?;
+        }
+        Command::Pagerank(a) => run_pagerank(&mut out, &mut export, &graph, &a.rank, &a.pagerank)?,
+        Command::Spectral(a) => run_spectral(&mut out, &mut export, &graph, &a.rank, &a.spectral, Vec::new())?,
+        Command::Communities(a) => run_communities(&mut out, &mut export, &graph, &a.rank, a.splits)?,
+        Command::All(a) => {
+            run_stats(&mut out, &mut export, &graph, &a.paths, a.seed.seed)?;
+            run_densest(&mut out, &mut export, &graph, &a.densest)?;
+            run_cores(&mut out, &mut export, &graph)?;
+            run_clustering(&mut out, &mut export, &graph)?;
+            run_distribution(&mut out, &mut export, &graph, &a.fit, a.seed.seed)?;
+            let variant = a.closeness.closeness.into();
+            let clos_rank = run_closeness(&mut out, &mut export, &graph, &a.rank, variant)?;
+            let betw_rank = run_betweenness(&mut out, &mut export, &graph, &a.rank, &a.betweenness, a.seed.seed)?;
+            run_pagerank(&mut out, &mut export, &graph, &a.rank, &a.pagerank)?;
+            let others = vec![("closeness", clos_rank), ("betweenness", betw_rank)];
+            run_spectral(&mut out, &mut export, &graph, &a.rank, &a.spectral, others)?;
+        }
+    }
+
+    if let Some(export) = export {
+        let manifest = export.finish()?;
+        eprintln!("Results written; manifest at {}", manifest.display());
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Load the graph once from the edge list at `path`.
+fn run_load<W: Write>(out: &mut W, path: &str) -> Result<UGraph> {
+    write_section(out, "Loading Graph")?;
+    let graph = graph_io::load_edge_list(path)?;
+    writeln!(
+        out,
         "Graph loaded: {} nodes, {} edges\n",
         graph.node_count(),
         graph.edge_count()
-    );
+    )?;
+    Ok(graph)
+}
+
+/// Load the ego-Facebook tarball and summarise each ego network.
+fn run_egos<W: Write>(out: &mut W, input: &InputArgs) -> Result<()> {
+    write_section(out, "Loading Ego Networks")?;
+    let (data, secs) = measure_time("Tarball parsing", || graph_io::load_ego_facebook(&input.path));
+    let data = data?;
+    writeln!(
+        out,
+        "Graph loaded: {} nodes, {} edges, {} ego networks\n",
+        data.graph.node_count(),
+        data.graph.edge_count(),
+        data.egos.len()
+    )?;
+
+    write_section(out, "Ego Networks")?;
+    let rows: Vec<_> = data.egos.iter().map(EgoSummaryRow::from).collect();
+    for r in &rows {
+        writeln!(
+            out,
+            "  ego {:>4} → {:>4} alters, {:>3} circles (largest {:>3}), {:>4} features",
+            r.ego, r.alters, r.circles, r.largest_circle, r.features
+        )?;
+    }
+    writeln!(out)?;
+
+    if let Some(dir) = &input.out_dir {
+        let mut export = RunWriter::create(
+            dir,
+            &input.path,
+            data.graph.node_count(),
+            data.graph.edge_count(),
+        )?;
+        export.record_table("ego_networks", &rows, &rows, secs)?;
+        let manifest = export.finish()?;
+        eprintln!("Results written; manifest at {}", manifest.display());
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Average shortest-path length: exact all-pairs statistics (diameter,
+/// radius, eccentricities) or a sampled estimate with SE and bootstrap CI.
+fn run_stats<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+    args: &PathArgs,
+    seed: u64,
+) -> Result<()> {
+    write_section(out, "Average Shortest-Path")?;
+
+    let Some(opts) = args.estimate_options(seed) else {
+        let (ps, secs) = measure_time("All-pairs BFS", || {
+            graph_analysis::path_length_stats(graph, PathSampling::Exact)
+        });
+        writeln!(out, "Average shortest-path length = {:.3} ({} sources)", ps.average_path_length, ps.sources)?;
+        writeln!(out, "Diameter = {}, radius = {}\n", ps.diameter, ps.radius)?;
+
+        if let Some(export) = export {
+            let report = PathLengthReport::from(&ps);
+            let rows = output::eccentricity_rows(&ps.eccentricities);
+            export.record_table("average_path", &report, &rows, secs)?;
+        }
+        return Ok(());
+    };
+
+    let (est, secs) = measure_time("BFS sampling", || match args.tolerance {
+        Some(tol) => graph_analysis::estimate_average_path_adaptive(graph, tol, &opts),
+        None => graph_analysis::estimate_average_path(graph, &opts),
+    });
+    writeln!(
+        out,
+        "Average shortest-path length ≈ {:.3} ± {:.3} (SE), {:.0}% CI [{:.3}, {:.3}] from {} sources\n",
+        est.estimate,
+        est.standard_error,
+        100.0 * opts.confidence,
+        est.ci_low,
+        est.ci_high,
+        est.sources
+    )?;
+
+    if let Some(export) = export {
+        export.record_json("average_path_estimate", &est, secs)?;
+    }
+    Ok(())
+}
 
-    // -- Average shortest-path (unweighted) via BFS sampling
-    print_section("Average Shortest-Path");
-    let avg = measure_time("BFS sampling", || {
-        graph_analysis::average_shortest_path(&graph)
+/// Densest subgraph (2-approx) via peeling algorithm.
+fn run_densest<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+    args: &DensestOpts,
+) -> Result<()> {
+    write_section(out, "Densest Subgraph (2-approx)")?;
+    let (peel, secs) = measure_time("Peeling algorithm", || {
+        graph_analysis::densest_subgraph_peel_trace(graph)
     });
-    println!("Average shortest-path length ≈ {:.3}\n", avg);
+    let ds = &peel.subgraph;
+    writeln!(out, "Density = {:.3} with {} nodes\n", ds.density, ds.nodes.len())?;
 
-    // -- Densest subgraph (2-approx) via peeling algorithm
-    print_section("Densest Subgraph (2-approx)");
-    let ds = measure_time("Peeling algorithm", || {
-        graph_analysis::densest_subgraph_peel(&graph)
+    if let Some(export) = export {
+        let mut rows: Vec<_> = ds.nodes.iter().map(|&node| NodeRow { node }).collect();
+        rows.sort_by_key(|r| r.node);
+        export.record_table("densest_subgraph", ds, &rows, secs)?;
+        let steps = output::peeling_rows(&peel);
+        export.record_table("peeling_curve", &steps, &steps, secs)?;
+    }
+
+    if let Some(passes) = args.greedy_pp {
+        write_section(out, &format!("Densest Subgraph (Greedy++, {} passes)", passes))?;
+        let (gpp, secs) = measure_time("Greedy++", || {
+            graph_analysis::densest_subgraph_greedy_plus_plus(graph, passes)
+        });
+        if let Some(last) = gpp.trace.last() {
+            writeln!(
+                out,
+                "Density = {:.3} with {} nodes; upper bound {:.3} (gap ≤ {:.2}%)\n",
+                last.best_density,
+                gpp.subgraph.nodes.len(),
+                last.upper_bound,
+                100.0 * (1.0 - last.best_density / last.upper_bound.max(f64::MIN_POSITIVE))
+            )?;
+        }
+        if let Some(export) = export {
+            export.record_table("densest_greedy_pp", &gpp, &gpp.trace, secs)?;
+        }
+    }
+
+    if !args.exact {
+        return Ok(());
+    }
+
+    write_section(out, "Densest Subgraph (exact)")?;
+    let (exact, secs) = measure_time("Goldberg max-flow", || {
+        graph_analysis::densest_subgraph_exact(graph)
+    });
+    let report = ExactDensestReport::new(&exact, ds.density, exact.certificate.verify(graph));
+    writeln!(
+        out,
+        "Density = {}/{} = {:.3} with {} nodes (max-flow iterations: {}, certificate {})",
+        report.edges,
+        report.nodes,
+        report.density,
+        report.nodes,
+        report.iterations,
+        if report.certificate_verified { "verified" } else { "FAILED" }
+    )?;
+    writeln!(out, "Peeling reaches {:.2}% of the optimum\n", 100.0 * report.approximation_ratio)?;
+
+    if let Some(export) = export {
+        let rows = output::orientation_rows(&exact.certificate);
+        export.record_table("densest_exact", &report, &rows, secs)?;
+    }
+    Ok(())
+}
+
+/// k-core and k-truss decompositions: degeneracy, core sizes, the maximal
+/// truss, and how both compare with the peeling densest subgraph.
+fn run_cores<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+) -> Result<()> {
+    write_section(out, "k-Core Decomposition")?;
+    let (cores, secs) = measure_time("Core decomposition", || graph_analysis::core_numbers(graph));
+    let max_core = cores.k_core(graph, cores.degeneracy);
+    let densest = graph_analysis::densest_subgraph_peel(graph);
+
+    let max_core_nodes = cores.k_core_nodes(cores.degeneracy);
+    let overlap = densest.nodes.iter().filter(|v| max_core_nodes.binary_search(v).is_ok()).count();
+    let union = max_core_nodes.len() + densest.nodes.len() - overlap;
+    let report = CoreReport {
+        degeneracy: cores.degeneracy,
+        max_core_nodes: max_core.node_count(),
+        max_core_edges: max_core.edge_count(),
+        max_core_density: max_core.edge_count() as f64 / max_core.node_count().max(1) as f64,
+        densest_nodes: densest.nodes.len(),
+        densest_density: densest.density,
+        overlap,
+        jaccard: if union > 0 { overlap as f64 / union as f64 } else { 1.0 },
+    };
+
+    let sizes = output::core_size_rows(&cores);
+    for row in sizes.iter().filter(|r| r.shell > 0) {
+        writeln!(out, "  core {:>3} → {:>5} nodes ({:>5} in the {}-core)", row.k, row.shell, row.core, row.k)?;
+    }
+    writeln!(
+        out,
+        "Degeneracy = {}; max-core has {} nodes, density {:.3}",
+        report.degeneracy, report.max_core_nodes, report.max_core_density
+    )?;
+    writeln!(
+        out,
+        "Densest subgraph ({} nodes, density {:.3}) shares {} nodes with the max-core (Jaccard {:.3})\n",
+        report.densest_nodes, report.densest_density, report.overlap, report.jaccard
+    )?;
+
+    if let Some(export) = export {
+        let rows = output::core_rows(&cores);
+        export.record_table("core_numbers", &report, &rows, secs)?;
+        export.record_table("core_sizes", &sizes, &sizes, secs)?;
+    }
+
+    write_section(out, "k-Truss Decomposition")?;
+    let (truss, secs) =
+        measure_time("Truss decomposition", || graph_analysis::truss_decomposition(graph));
+    let max_truss = truss.k_truss(graph, truss.max_truss);
+    let report = TrussReport {
+        max_truss: truss.max_truss,
+        max_truss_nodes: max_truss.node_count(),
+        max_truss_edges: max_truss.edge_count(),
+        max_truss_density: max_truss.edge_count() as f64 / max_truss.node_count().max(1) as f64,
+        max_core_density: report.max_core_density,
+        densest_density: report.densest_density,
+    };
+    let dist = truss.truss_size_distribution();
+    for (t, cnt) in dist.iter().sorted_by_key(|&(t, _)| *t) {
+        writeln!(out, "  truss {:>3} → {:>6} edges", t, cnt)?;
+    }
+    writeln!(
+        out,
+        "Max truss k = {}: {} nodes, {} edges, density {:.3} (max-core {:.3}, densest {:.3})\n",
+        report.max_truss,
+        report.max_truss_nodes,
+        report.max_truss_edges,
+        report.max_truss_density,
+        report.max_core_density,
+        report.densest_density
+    )?;
+
+    if let Some(export) = export {
+        let rows = output::truss_rows(&truss);
+        export.record_table("truss_edges", &report, &rows, secs)?;
+    }
+    Ok(())
+}
+
+/// Triangles, clustering coefficients and the clustering-vs-degree curve.
+fn run_clustering<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+) -> Result<()> {
+    write_section(out, "Clustering")?;
+    let (stats, secs) =
+        measure_time("Triangle counting", || graph_analysis::clustering_stats(graph));
+    let report = ClusteringReport::from(&stats);
+    writeln!(out, "Triangles = {}", report.triangles)?;
+    writeln!(out, "Average clustering = {:.4}", report.average_clustering)?;
+    writeln!(out, "Transitivity = {:.4}\n", report.transitivity)?;
+
+    write_section(out, "Clustering by Degree C(k)")?;
+    let curve = output::degree_clustering_rows(&stats);
+    for r in &curve {
+        writeln!(out, "  degree {:>4} → C = {:.4}", r.degree, r.clustering)?;
+    }
+    writeln!(out)?;
+
+    if let Some(export) = export {
+        let rows = output::clustering_rows(&stats);
+        export.record_table("clustering", &report, &rows, secs)?;
+        export.record_table("clustering_by_degree", &curve, &curve, secs)?;
+    }
+    Ok(())
+}
+
+/// PageRank and, with `--personalize`, personalized PageRank; top `top` nodes.
+fn run_pagerank<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+    rank: &RankArgs,
+    args: &PageRankOpts,
+) -> Result<()> {
+    let opts = args.options();
+    write_section(out, &format!("PageRank (top {}, damping {})", rank.top, opts.damping))?;
+    let (pr, secs) = measure_time("PageRank", || graph_analysis::pagerank(graph, &opts));
+    let report = PageRankReport { options: opts, seeds: None, convergence: pr.convergence };
+    write_pagerank(out, export, "pagerank", &report, &pr.scores, rank.top, secs)?;
+
+    if args.personalize.is_empty() {
+        return Ok(());
+    }
+    write_section(out, &format!("Personalized PageRank from {:?} (top {})", args.personalize, rank.top))?;
+    let (ppr, secs) = measure_time("Personalized PageRank", || {
+        graph_analysis::personalized_pagerank(graph, &args.personalize, &opts)
+    });
+    let Some(ppr) = ppr else {
+        writeln!(out, "None of the seed nodes is in the graph\n")?;
+        return Ok(());
+    };
+    let report = PageRankReport {
+        options: opts,
+        seeds: Some(args.personalize.clone()),
+        convergence: ppr.convergence,
+    };
+    write_pagerank(out, export, "personalized_pagerank", &report, &ppr.scores, rank.top, secs)
+}
+
+/// Print a PageRank convergence line and ranking, and export them as `name`.
+fn write_pagerank<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    name: &str,
+    report: &PageRankReport,
+    scores: &HashMap<usize, f64>,
+    top: usize,
+    secs: f64,
+) -> Result<()> {
+    let ranked = write_converged_ranking(out, &report.convergence, scores, top)?;
+    if let Some(export) = export {
+        export.record_table(name, report, &ranked, secs)?;
+    }
+    Ok(())
+}
+
+/// Eigenvector and Katz centrality (top `top` nodes each), then how many
+/// top-`top` nodes they share with each other and with the `others`
+/// rankings already computed (closeness and betweenness under `all`).
+/// A disconnected graph or a too-large Katz α is reported, not fatal.
+fn run_spectral<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+    rank: &RankArgs,
+    args: &SpectralOpts,
+    others: Vec<(&str, Vec<RankedNode>)>,
+) -> Result<()> {
+    let top = rank.top;
+    let mut rankings = others;
+
+    write_section(out, &format!("Eigenvector Centrality (top {})", top))?;
+    let opts = PowerIterationOptions::default();
+    let (eig, secs) = measure_time("Eigenvector power iteration", || {
+        graph_analysis::eigenvector_centrality(graph, &opts)
+    });
+    match eig {
+        Ok(eig) => {
+            let report = EigenvectorReport {
+                options: opts,
+                eigenvalue: eig.eigenvalue,
+                convergence: eig.convergence,
+            };
+            writeln!(out, "λ_max ≈ {:.4}", report.eigenvalue)?;
+            let ranked = write_converged_ranking(out, &report.convergence, &eig.scores, top)?;
+            if let Some(export) = export {
+                export.record_table("eigenvector", &report, &ranked, secs)?;
+            }
+            rankings.push(("eigenvector", ranked));
+        }
+        Err(e) => writeln!(out, "Skipped: {}\n", e)?,
+    }
+
+    write_section(out, &format!("Katz Centrality (top {})", top))?;
+    let (katz, secs) = measure_time("Katz power iteration", || {
+        graph_analysis::katz_centrality(graph, &args.katz_options())
     });
-    println!("Density = {:.3} with {} nodes\n", ds.density, ds.nodes.len());
+    match katz {
+        Ok(katz) => {
+            let report = KatzReport::from(&katz);
+            writeln!(
+                out,
+                "α = {:.6} (1/λ_max = {:.6}), β = {}",
+                report.alpha,
+                1.0 / report.spectral_radius,
+                report.beta
+            )?;
+            let ranked = write_converged_ranking(out, &report.convergence, &katz.scores, top)?;
+            if let Some(export) = export {
+                export.record_table("katz", &report, &ranked, secs)?;
+            }
+            rankings.push(("katz", ranked));
+        }
+        Err(e) => writeln!(out, "Skipped: {}\n", e)?,
+    }
+
+    if rankings.len() < 2 {
+        return Ok(());
+    }
+    write_section(out, &format!("Top-{} overlap between centralities", top))?;
+    let named: Vec<(&str, &[RankedNode])> =
+        rankings.iter().map(|(name, r)| (*name, r.as_slice())).collect();
+    let overlaps = output::top_overlaps(&named, top);
+    for row in &overlaps {
+        writeln!(out, "  {:>11} ∩ {:<11} {:>3} / {}", row.first, row.second, row.shared, row.top)?;
+    }
+    writeln!(out)?;
 
-    // -- 1-hop degree distribution histogram
-    print_section("1-Hop Degree Distribution");
-    let one_hop = graph_analysis::degree_distribution(&graph);
+    if let Some(export) = export {
+        export.record_table("centrality_overlap", &overlaps, &overlaps, 0.0)?;
+    }
+    Ok(())
+}
+
+/// Print how an iterative method ended and the top `top` of `scores`;
+/// returns the full ranking.
+fn write_converged_ranking<W: Write>(
+    out: &mut W,
+    c: &ConvergenceReport,
+    scores: &HashMap<usize, f64>,
+    top: usize,
+) -> Result<Vec<RankedNode>> {
+    writeln!(
+        out,
+        "{} after {} iterations (L1 residual {:.2e})",
+        if c.converged { "Converged" } else { "NOT converged" },
+        c.iterations,
+        c.residual
+    )?;
+    let ranked = output::rank_scores(scores);
+    for r in ranked.iter().take(top) {
+        writeln!(out, "  node {:>4} → {:.6}", r.node, r.score)?;
+    }
+    writeln!(out)?;
+    Ok(ranked)
+}
+
+/// Top edges by betweenness, then the Girvan–Newman dendrogram and its
+/// maximum-modularity partition.
+fn run_communities<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+    args: &RankArgs,
+    max_splits: Option<usize>,
+) -> Result<()> {
+    let top = args.top;
+    write_section(out, &format!("Edge Betweenness (top {})", top))?;
+    let (edge_betw, eb_secs) = measure_time("Edge betweenness", || {
+        graph_analysis::edge_betweenness_centrality(graph)
+    });
+    let ranked = output::rank_edges(&edge_betw);
+    for r in ranked.iter().take(top) {
+        writeln!(out, "  edge {:>4} – {:<4} → {:.4}", r.u, r.v, r.score)?;
+    }
+    writeln!(out)?;
+
+    write_section(out, "Girvan–Newman Communities")?;
+    let (gn, gn_secs) =
+        measure_time("Girvan–Newman", || graph_analysis::girvan_newman(graph, max_splits));
+    let report = GirvanNewmanReport::from(&gn);
+    writeln!(
+        out,
+        "Start: {} components, modularity {:.4}",
+        report.initial_communities, report.initial_modularity
+    )?;
+    for s in &report.splits {
+        writeln!(
+            out,
+            "  after {:>5} removals: {:>3} communities (split {} → {}, {} nodes), Q = {:.4}",
+            s.edges_removed, s.communities, s.parent, s.child, s.moved, s.modularity
+        )?;
+    }
+    let mut sizes: HashMap<usize, usize> = HashMap::new();
+    for &c in gn.best_partition.values() {
+        *sizes.entry(c).or_insert(0) += 1;
+    }
+    let sizes: Vec<usize> = sizes.into_values().sorted_by(|a, b| b.cmp(a)).collect();
+    writeln!(
+        out,
+        "Best partition after {} splits: {} communities, modularity {:.4}, sizes {:?}\n",
+        report.best_level,
+        sizes.len(),
+        report.best_modularity,
+        sizes
+    )?;
+
+    if let Some(export) = export {
+        export.record_table("edge_betweenness", &ranked, &ranked, eb_secs)?;
+        let rows = output::community_rows(&gn.best_partition);
+        export.record_table("girvan_newman", &report, &rows, gn_secs)?;
+    }
+    Ok(())
+}
+
+/// 1-hop / 2-hop histograms and power-law fit on 1-hop degrees, with an
+/// optional bootstrap goodness-of-fit test seeded by `seed`.
+fn run_distribution<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+    args: &FitArgs,
+    seed: u64,
+) -> Result<()> {
+    write_section(out, "1-Hop Degree Distribution")?;
+    let (one_hop, one_secs) = time_it(|| graph_analysis::degree_distribution(graph));
     for (deg, cnt) in one_hop.iter().sorted_by_key(|&(d, _)| *d) {
-        println!("  degree {:>3} → {:>5} nodes", deg, cnt);
+        writeln!(out, "  degree {:>3} → {:>5} nodes", deg, cnt)?;
+    }
+    writeln!(out)?;
+    write_summary(out, export, "degree", "1-Hop Degrees", &one_hop)?;
+
+    write_section(out, "Power-Law Fit (1-Hop Degrees)")?;
+    let (fit, fit_secs) = time_it(|| match args.k_min {
+        Some(k_min) => stats::fit_power_law(&one_hop, k_min, args.method()),
+        None => stats::fit_power_law_tail(&one_hop, args.method()),
+    });
+    match &fit {
+        Some(fit) => writeln!(
+            out,
+            "Estimated power-law exponent α ≈ {:.3} ± {:.3} ({:?} MLE over {} nodes with degree ≥ {}{})\n\
+             KS distance to the fitted tail: {:.4}\n",
+            fit.alpha,
+            fit.std_error,
+            fit.method,
+            fit.tail_size,
+            fit.k_min,
+            if args.k_min.is_none() { ", chosen by KS minimization" } else { "" },
+            fit.ks_distance
+        )?,
+        None => writeln!(out, "Not enough distinct degrees to fit a power law\n")?,
     }
-    println!();
+    let gof = match &fit {
+        Some(fit) if args.gof > 0 => {
+            let opts = GoodnessOfFitOptions {
+                replicates: args.gof,
+                seed,
+                select_k_min: args.k_min.is_none(),
+            };
+            let (gof, secs) = measure_time("Bootstrap goodness of fit", || {
+                stats::power_law_gof(&one_hop, fit, &opts)
+            });
+            writeln!(
+                out,
+                "Goodness of fit: p = {:.3} ({} of {} synthetic datasets fit worse); \
+                 a power law is {}\n",
+                gof.p_value,
+                gof.exceeding,
+                gof.replicates,
+                if gof.p_value > 0.1 { "plausible" } else { "ruled out (p ≤ 0.1)" }
+            )?;
+            Some((gof, secs))
+        }
+        _ => None,
+    };
 
-    // -- Discrete-MLE power-law fitting on 1-hop degrees
-    print_section("Power-Law Fit (1-Hop Degrees)");
-    let alpha_hat = stats::mle_power_law_exponent(&one_hop, /*k_min=*/ 1);
-    println!("Estimated power-law exponent α ≈ {:.3}\n", alpha_hat);
+    if let (Some(fit), true) = (&fit, args.compare) {
+        let name = "power_law_comparison";
+        run_comparison(out, export, name, "1-Hop Degrees", &one_hop, fit, args.k_min.is_none())?;
+    }
 
-    // -- 2-hop neighbor count distribution histogram
-    print_section("2-Hop Neighbor Distribution");
-    let two_hop = graph_analysis::two_hop_distribution(&graph);
+    write_section(out, "2-Hop Neighbor Distribution")?;
+    let (two_hop, two_secs) =
+        measure_time("2-hop BFS", || graph_analysis::two_hop_distribution(graph));
     for (h2, cnt) in two_hop.iter().sorted_by_key(|&(h2, _)| *h2) {
-        println!("  {:>3} two-hop neighbors → {:>5} nodes", h2, cnt);
+        writeln!(out, "  {:>3} two-hop neighbors → {:>5} nodes", h2, cnt)?;
     }
-    println!();
+    writeln!(out)?;
+    write_summary(out, export, "two_hop", "2-Hop Counts", &two_hop)?;
 
-    // -- Closeness centrality (top 10)
-    print_section("Closeness Centrality (top 10)");
-    let mut clos_vec: Vec<_> = graph_analysis::closeness_centrality(&graph)
-        .into_iter()
-        .collect();
-    clos_vec.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-    for (node, score) in clos_vec.into_iter().take(10) {
-        println!("  node {:>4} → {:.4}", node, score);
+    if args.compare {
+        let fit = match args.k_min {
+            Some(k_min) => stats::fit_power_law(&two_hop, k_min, args.method()),
+            None => stats::fit_power_law_tail(&two_hop, args.method()),
+        };
+        match &fit {
+            Some(fit) => {
+                let name = "two_hop_power_law_comparison";
+                run_comparison(out, export, name, "2-Hop Counts", &two_hop, fit, args.k_min.is_none())?
+            }
+            None => writeln!(out, "Not enough distinct 2-hop counts to fit a power law\n")?,
+        }
     }
-    println!();
 
-    // -- Betweenness centrality (top 10)
-    print_section("Betweenness Centrality (top 10)");
-    let mut betw_vec: Vec<_> = graph_analysis::betweenness_centrality(&graph)
-        .into_iter()
-        .collect();
-    betw_vec.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-    for (node, score) in betw_vec.into_iter().take(10) {
-        println!("  node {:>4} → {:.4}", node, score);
+    if let Some(export) = export {
+        let bins = output::histogram_bins(&one_hop);
+        export.record_table("degree_distribution", &bins, &bins, one_secs.as_secs_f64())?;
+        if let Some(fit) = &fit {
+            let report = PowerLawReport { k_min_selected: args.k_min.is_none(), ..fit.into() };
+            export.record_json("power_law", &report, fit_secs.as_secs_f64())?;
+        }
+        if let Some((gof, secs)) = &gof {
+            export.record_json("power_law_gof", gof, *secs)?;
+        }
+        let bins = output::histogram_bins(&two_hop);
+        export.record_table("two_hop_distribution", &bins, &bins, two_secs)?;
     }
-    println!();
+    Ok(())
+}
+
+/// Descriptive statistics of `hist`, printed under `label` and exported as
+/// `<name>_summary` (summary + CCDF rows) and `<name>_log_bins`.
+fn write_summary<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    name: &str,
+    label: &str,
+    hist: &HashMap<usize, usize>,
+) -> Result<()> {
+    const BINS_PER_DECADE: usize = 5;
+    write_section(out, &format!("Summary Statistics ({})", label))?;
+    let (hist, secs) = time_it(|| Histogram::new(hist));
+    let Some(hist) = hist else {
+        writeln!(out, "Empty distribution\n")?;
+        return Ok(());
+    };
+    let summary = hist.summary();
+    writeln!(
+        out,
+        "n = {}, {} distinct values in [{}, {}], mode {}\n\
+         mean {:.3}, std dev {:.3}, skewness {:.3}, excess kurtosis {:.3}\n\
+         Gini {:.4}, entropy {:.3} bits",
+        summary.count,
+        summary.distinct,
+        summary.min,
+        summary.max,
+        summary.mode,
+        summary.mean,
+        summary.std_dev,
+        summary.skewness,
+        summary.excess_kurtosis,
+        summary.gini,
+        summary.entropy_bits
+    )?;
+    let quantiles = summary.quantiles.iter().map(|p| format!("q{}={:.1}", p.q, p.value)).join(", ");
+    writeln!(out, "quantiles: {}\n", quantiles)?;
 
+    if let Some(export) = export {
+        let secs = secs.as_secs_f64();
+        export.record_table(&format!("{}_summary", name), &summary, &hist.ccdf(), secs)?;
+        let bins = hist.log_bins(BINS_PER_DECADE);
+        export.record_table(&format!("{}_log_bins", name), &bins, &bins, secs)?;
+    }
+    Ok(())
+}
+
+/// Likelihood-ratio tests of the power law `fit` of `hist` against every
+/// alternative, printed under `label` and exported as `name`.
+fn run_comparison<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    name: &str,
+    label: &str,
+    hist: &HashMap<usize, usize>,
+    fit: &PowerLawFit,
+    k_min_selected: bool,
+) -> Result<()> {
+    const LEVEL: f64 = 0.1;
+    write_section(out, &format!("Power Law vs Alternatives ({}, k ≥ {})", label, fit.k_min))?;
+    writeln!(
+        out,
+        "power law (α = {:.4}): log-likelihood {:.2} over {} observations",
+        fit.alpha, fit.log_likelihood, fit.tail_size
+    )?;
+    let (comparisons, secs) = measure_time("Alternative fits", || {
+        Alternative::ALL
+            .iter()
+            .filter_map(|&alt| stats::compare_to_power_law(hist, fit, alt))
+            .collect::<Vec<_>>()
+    });
+    let rows = output::comparison_rows(&comparisons, LEVEL);
+    for r in &rows {
+        writeln!(
+            out,
+            "  {:<58} R = {:>10.2}  R/(σ√n) = {:>7.2}  p = {:.3}  → {}",
+            r.model, r.ratio, r.normalized_ratio, r.p_value, r.favoured
+        )?;
+    }
+    writeln!(out, "(R > 0 favours the power law; a side is favoured only when p < {})\n", LEVEL)?;
+
+    if let Some(export) = export {
+        let report = ComparisonReport {
+            power_law: PowerLawReport { k_min_selected, ..fit.into() },
+            comparisons,
+        };
+        export.record_table(name, &report, &rows, secs)?;
+    }
     Ok(())
 }
+
+/// Closeness centrality in the given `variant`, top `top` nodes; returns the
+/// full ranking for comparison with the spectral measures.
+fn run_closeness<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+    args: &RankArgs,
+    variant: ClosenessVariant,
+) -> Result<Vec<RankedNode>> {
+    let top = args.top;
+    let title = match variant {
+        ClosenessVariant::Reachable => "Closeness Centrality",
+        ClosenessVariant::WassermanFaust => "Closeness Centrality (Wasserman–Faust)",
+        ClosenessVariant::Harmonic => "Harmonic Centrality",
+    };
+    write_section(out, &format!("{} (top {})", title, top))?;
+    let (closeness, clos_secs) = measure_time("Closeness BFS", || {
+        graph_analysis::closeness_centrality_with(graph, variant)
+    });
+    let clos_rank = output::rank_scores(&closeness);
+    for r in clos_rank.iter().take(top) {
+        writeln!(out, "  node {:>4} → {:.4}", r.node, r.score)?;
+    }
+    writeln!(out)?;
+
+    if let Some(export) = export {
+        export.record_table("closeness", &clos_rank, &clos_rank, clos_secs)?;
+    }
+    Ok(clos_rank)
+}
+
+/// Betweenness centrality, top `top` nodes: exact Brandes unless `betw`
+/// selects a sampling scheme. Returns the full ranking.
+fn run_betweenness<W: Write>(
+    out: &mut W,
+    export: &mut Option<RunWriter>,
+    graph: &UGraph,
+    args: &RankArgs,
+    betw: &BetweennessOpts,
+    seed: u64,
+) -> Result<Vec<RankedNode>> {
+    let opts = betw.options();
+    let top = args.top;
+    write_section(out, &format!("Betweenness Centrality (top {})", top))?;
+    let Some(sampling) = betw.sampling(seed) else {
+        let (betweenness, betw_secs) = measure_time("Brandes betweenness", || {
+            graph_analysis::betweenness_centrality_with(graph, &opts)
+        });
+        let betw_rank = output::rank_scores(&betweenness);
+        for r in betw_rank.iter().take(top) {
+            writeln!(out, "  node {:>4} → {:.4}", r.node, r.score)?;
+        }
+        writeln!(out)?;
+
+        if let Some(export) = export {
+            export.record_table("betweenness", &betw_rank, &betw_rank, betw_secs)?;
+        }
+        return Ok(betw_rank);
+    };
+
+    let (est, betw_secs) = measure_time("Sampled betweenness", || {
+        graph_analysis::betweenness_sampled(graph, sampling)
+    });
+    let mut est = est?;
+    let scale = opts.scale(graph.node_count());
+    est.scores.values_mut().for_each(|b| *b *= scale);
+    let report = SampledBetweennessReport::from(&est);
+    match sampling {
+        BetweennessSampling::Pivots { .. } => {
+            writeln!(out, "Estimated from {} pivot sources", report.samples)?
+        }
+        BetweennessSampling::PathSampling { epsilon, delta, .. } => writeln!(
+            out,
+            "Estimated from {} sampled shortest paths (vertex diameter ≤ {}); \
+             within ±{} of the normalised score with probability ≥ {}",
+            report.samples,
+            report.vertex_diameter_bound.unwrap_or(0),
+            epsilon,
+            1.0 - delta
+        )?,
+    }
+    let betw_rank = output::rank_scores(&est.scores);
+    for r in betw_rank.iter().take(top) {
+        writeln!(out, "  node {:>4} → {:.4}", r.node, r.score)?;
+    }
+    writeln!(out)?;
+
+    if let Some(export) = export {
+        export.record_table("betweenness_sampled", &report, &betw_rank, betw_secs)?;
+    }
+    Ok(betw_rank)
+}
@@ -0,0 +1,709 @@
+//! Module `output`: machine-readable JSON / CSV export of analysis results.
+//!
+//! Every analysis writes `<name>.json`; tabular results (histograms,
+//! rankings, node lists) additionally write `<name>.csv`. A top-level
+//! `manifest.json` lists the input graph, every analysis that ran, its
+//! wall-clock time and the files it produced.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::graph_analysis::{
+    BetweennessEstimate, BetweennessSampling, ClusteringStats, ConvergenceReport,
+    CoreDecomposition, DensityCertificate, ExactDensestResult, GirvanNewmanResult, KatzResult,
+    PageRankOptions, PathLengthStats, PathSampling, PeelingResult, PowerIterationOptions,
+    TrussDecomposition,
+};
+use crate::io::EgoNetwork;
+use crate::stats::{LikelihoodRatio, PowerLawFit, PowerLawMethod};
+
+/// Bumped whenever a field is renamed or removed from any output file.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// One histogram bin: `count` nodes share the same `value`
+/// (degree, two-hop neighbour count, …).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistogramBin {
+    pub value: usize,
+    pub count: usize,
+}
+
+/// Flatten a `value → count` histogram into bins sorted by `value`.
+pub fn histogram_bins(hist: &HashMap<usize, usize>) -> Vec<HistogramBin> {
+    let mut bins: Vec<_> = hist
+        .iter()
+        .map(|(&value, &count)| HistogramBin { value, count })
+        .collect();
+    bins.sort_by_key(|b| b.value);
+    bins
+}
+
+/// One row of a centrality ranking (`rank` starts at 1).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankedNode {
+    pub rank: usize,
+    pub node: usize,
+    pub score: f64,
+}
+
+/// Rank a `node payload → score` map by descending score.
+/// Ties are broken by ascending node payload so the order is deterministic.
+pub fn rank_scores(scores: &HashMap<usize, f64>) -> Vec<RankedNode> {
+    let mut pairs: Vec<_> = scores.iter().map(|(&n, &s)| (n, s)).collect();
+    pairs.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    pairs
+        .into_iter()
+        .enumerate()
+        .map(|(i, (node, score))| RankedNode { rank: i + 1, node, score })
+        .collect()
+}
+
+/// Sampled betweenness summary (`betweenness_sampled.json`); the ranking
+/// goes to `betweenness_sampled.csv` as [`RankedNode`]s.
+#[derive(Debug, Clone, Serialize)]
+pub struct SampledBetweennessReport {
+    pub sampling: BetweennessSampling,
+    pub samples: usize,
+    pub vertex_diameter_bound: Option<usize>,
+}
+
+impl From<&BetweennessEstimate> for SampledBetweennessReport {
+    fn from(est: &BetweennessEstimate) -> Self {
+        SampledBetweennessReport {
+            sampling: est.sampling,
+            samples: est.samples,
+            vertex_diameter_bound: est.vertex_diameter_bound,
+        }
+    }
+}
+
+/// PageRank summary (`pagerank.json` / `personalized_pagerank.json`); the
+/// ranking goes to the matching CSV as [`RankedNode`]s.
+#[derive(Debug, Clone, Serialize)]
+pub struct PageRankReport {
+    pub options: PageRankOptions,
+    /// Teleport targets for personalized PageRank
+    pub seeds: Option<Vec<usize>>,
+    pub convergence: ConvergenceReport,
+}
+
+/// Eigenvector centrality summary (`eigenvector.json`); the ranking goes to
+/// `eigenvector.csv` as [`RankedNode`]s.
+#[derive(Debug, Clone, Serialize)]
+pub struct EigenvectorReport {
+    pub options: PowerIterationOptions,
+    pub eigenvalue: f64,
+    pub convergence: ConvergenceReport,
+}
+
+/// Katz centrality summary (`katz.json`); the ranking goes to `katz.csv` as
+/// [`RankedNode`]s.
+#[derive(Debug, Clone, Serialize)]
+pub struct KatzReport {
+    pub alpha: f64,
+    pub beta: f64,
+    pub spectral_radius: f64,
+    pub convergence: ConvergenceReport,
+}
+
+impl From<&KatzResult> for KatzReport {
+    fn from(katz: &KatzResult) -> Self {
+        KatzReport {
+            alpha: katz.alpha,
+            beta: katz.beta,
+            spectral_radius: katz.spectral_radius,
+            convergence: katz.convergence.clone(),
+        }
+    }
+}
+
+/// One row of `centrality_overlap.csv`: how many of the top-k nodes two
+/// centrality rankings share.
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlapRow {
+    pub first: String,
+    pub second: String,
+    pub top: usize,
+    pub shared: usize,
+}
+
+/// Pairwise top-`k` overlaps between named rankings, in input order.
+pub fn top_overlaps(rankings: &[(&str, &[RankedNode])], k: usize) -> Vec<OverlapRow> {
+    let mut rows = Vec::new();
+    for (i, (first, a)) in rankings.iter().enumerate() {
+        for (second, b) in &rankings[i + 1..] {
+            let shared = a
+                .iter()
+                .take(k)
+                .filter(|r| b.iter().take(k).any(|q| q.node == r.node))
+                .count();
+            rows.push(OverlapRow {
+                first: first.to_string(),
+                second: second.to_string(),
+                top: k,
+                shared,
+            });
+        }
+    }
+    rows
+}
+
+/// Average shortest-path summary (`average_path.json`); the per-node
+/// eccentricities go to `average_path.csv` as [`EccentricityRow`]s.
+#[derive(Debug, Clone, Serialize)]
+pub struct PathLengthReport {
+    pub sampling: PathSampling,
+    pub sources: usize,
+    pub reachable_pairs: u64,
+    pub average_path_length: f64,
+    pub diameter: usize,
+    pub radius: usize,
+}
+
+impl From<&PathLengthStats> for PathLengthReport {
+    fn from(stats: &PathLengthStats) -> Self {
+        PathLengthReport {
+            sampling: stats.sampling,
+            sources: stats.sources,
+            reachable_pairs: stats.reachable_pairs,
+            average_path_length: stats.average_path_length,
+            diameter: stats.diameter,
+            radius: stats.radius,
+        }
+    }
+}
+
+/// Eccentricity of one BFS source, sorted by node payload.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EccentricityRow {
+    pub node: usize,
+    pub eccentricity: usize,
+}
+
+/// Flatten a `node → eccentricity` map into rows sorted by node.
+pub fn eccentricity_rows(ecc: &HashMap<usize, usize>) -> Vec<EccentricityRow> {
+    let mut rows: Vec<_> = ecc
+        .iter()
+        .map(|(&node, &eccentricity)| EccentricityRow { node, eccentricity })
+        .collect();
+    rows.sort_by_key(|r| r.node);
+    rows
+}
+
+/// Power-law fit summary (`power_law.json`).
+#[derive(Debug, Clone, Serialize)]
+pub struct PowerLawReport {
+    pub method: PowerLawMethod,
+    pub k_min: usize,
+    /// Whether `k_min` was chosen by KS minimization rather than given
+    pub k_min_selected: bool,
+    pub tail_size: usize,
+    pub alpha: f64,
+    pub std_error: f64,
+    pub ks_distance: f64,
+}
+
+impl From<&PowerLawFit> for PowerLawReport {
+    fn from(fit: &PowerLawFit) -> Self {
+        PowerLawReport {
+            method: fit.method,
+            k_min: fit.k_min,
+            k_min_selected: false,
+            tail_size: fit.tail_size,
+            alpha: fit.alpha,
+            std_error: fit.std_error,
+            ks_distance: fit.ks_distance,
+        }
+    }
+}
+
+/// Likelihood-ratio comparisons of a power-law fit with every alternative
+/// (`power_law_comparison.json`, `two_hop_power_law_comparison.json`).
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonReport {
+    pub power_law: PowerLawReport,
+    pub comparisons: Vec<LikelihoodRatio>,
+}
+
+/// One row of a comparison CSV.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonRow {
+    pub model: String,
+    pub log_likelihood: f64,
+    pub ratio: f64,
+    pub normalized_ratio: f64,
+    pub p_value: f64,
+    /// "power law", "alternative" or "neither" at the given level
+    pub favoured: String,
+}
+
+/// One [`ComparisonRow`] per comparison, judged at significance `level`.
+pub fn comparison_rows(comparisons: &[LikelihoodRatio], level: f64) -> Vec<ComparisonRow> {
+    comparisons
+        .iter()
+        .map(|c| ComparisonRow {
+            model: c.model.to_string(),
+            log_likelihood: c.log_likelihood,
+            ratio: c.ratio,
+            normalized_ratio: c.normalized_ratio,
+            p_value: c.p_value,
+            favoured: c.favoured(level).unwrap_or("neither").to_string(),
+        })
+        .collect()
+}
+
+/// Single-column CSV row used for node lists.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeRow {
+    pub node: usize,
+}
+
+/// One peeling step (`peeling_curve.csv`): before `node` is removed,
+/// `remaining` nodes are left with the given `density`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeelingStep {
+    pub step: usize,
+    pub node: usize,
+    pub remaining: usize,
+    pub density: f64,
+}
+
+/// Flatten a peeling trace into one row per removal.
+pub fn peeling_rows(peel: &PeelingResult) -> Vec<PeelingStep> {
+    let n = peel.order.len();
+    peel.order
+        .iter()
+        .zip(&peel.density_curve)
+        .enumerate()
+        .map(|(step, (&node, &density))| PeelingStep {
+            step,
+            node,
+            remaining: n - step,
+            density,
+        })
+        .collect()
+}
+
+/// Exact densest-subgraph summary (`densest_exact.json`); the certificate's
+/// edge orientation goes to `densest_exact.csv` as [`OrientationRow`]s.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExactDensestReport {
+    pub density: f64,
+    pub edges: usize,
+    pub nodes: usize,
+    pub iterations: usize,
+    pub certificate_verified: bool,
+    pub peel_density: f64,
+    /// peel density / exact density (≥ 0.5 by Charikar's bound)
+    pub approximation_ratio: f64,
+}
+
+impl ExactDensestReport {
+    pub fn new(exact: &ExactDensestResult, peel_density: f64, certificate_verified: bool) -> Self {
+        let density = exact.subgraph.density;
+        ExactDensestReport {
+            density,
+            edges: exact.certificate.edges,
+            nodes: exact.certificate.nodes,
+            iterations: exact.iterations,
+            certificate_verified,
+            peel_density,
+            approximation_ratio: if density > 0.0 { peel_density / density } else { 1.0 },
+        }
+    }
+}
+
+/// One edge of a density certificate: `share_u / nodes` is charged to `u`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrientationRow {
+    pub u: usize,
+    pub v: usize,
+    pub share_u: usize,
+    pub share_v: usize,
+}
+
+/// Flatten a certificate's orientation into CSV rows.
+pub fn orientation_rows(cert: &DensityCertificate) -> Vec<OrientationRow> {
+    cert.orientation
+        .iter()
+        .map(|&(u, v, share_u)| OrientationRow { u, v, share_u, share_v: cert.nodes - share_u })
+        .collect()
+}
+
+/// k-core summary (`core_numbers.json`), comparing the max-core with the
+/// peeling densest subgraph.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoreReport {
+    pub degeneracy: usize,
+    pub max_core_nodes: usize,
+    pub max_core_edges: usize,
+    pub max_core_density: f64,
+    pub densest_nodes: usize,
+    pub densest_density: f64,
+    /// nodes shared by the max-core and the densest subgraph
+    pub overlap: usize,
+    /// overlap / size of their union
+    pub jaccard: f64,
+}
+
+/// Core number of one node (`core_numbers.csv`), sorted by node.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoreRow {
+    pub node: usize,
+    pub core: usize,
+}
+
+/// Flatten core numbers into rows sorted by node.
+pub fn core_rows(cores: &CoreDecomposition) -> Vec<CoreRow> {
+    let mut rows: Vec<_> = cores
+        .core_numbers
+        .iter()
+        .map(|(&node, &core)| CoreRow { node, core })
+        .collect();
+    rows.sort_by_key(|r| r.node);
+    rows
+}
+
+/// Core-size distribution (`core_sizes.csv`): `shell` nodes have core
+/// number exactly `k`, `core` nodes have core number ≥ `k`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoreSizeRow {
+    pub k: usize,
+    pub shell: usize,
+    pub core: usize,
+}
+
+/// One row per k = 0..=degeneracy.
+pub fn core_size_rows(cores: &CoreDecomposition) -> Vec<CoreSizeRow> {
+    let shells = cores.core_size_distribution();
+    cores
+        .core_sizes()
+        .into_iter()
+        .enumerate()
+        .map(|(k, core)| CoreSizeRow { k, shell: shells.get(&k).copied().unwrap_or(0), core })
+        .collect()
+}
+
+/// k-truss summary (`truss_edges.json`): the maximal truss against the
+/// max-core and the peeling densest subgraph.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrussReport {
+    pub max_truss: usize,
+    pub max_truss_nodes: usize,
+    pub max_truss_edges: usize,
+    pub max_truss_density: f64,
+    pub max_core_density: f64,
+    pub densest_density: f64,
+}
+
+/// Trussness of one edge (`truss_edges.csv`), with `u < v`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrussRow {
+    pub u: usize,
+    pub v: usize,
+    pub trussness: usize,
+}
+
+/// Flatten edge trussness into rows sorted by `(u, v)`.
+pub fn truss_rows(truss: &TrussDecomposition) -> Vec<TrussRow> {
+    let mut rows: Vec<_> = truss
+        .trussness
+        .iter()
+        .map(|(&(u, v), &trussness)| TrussRow { u, v, trussness })
+        .collect();
+    rows.sort_by_key(|r| (r.u, r.v));
+    rows
+}
+
+/// Clustering summary (`clustering.json`); per-node values go to
+/// `clustering.csv` as [`ClusteringRow`]s.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusteringReport {
+    pub triangles: usize,
+    pub average_clustering: f64,
+    pub transitivity: f64,
+}
+
+impl From<&ClusteringStats> for ClusteringReport {
+    fn from(stats: &ClusteringStats) -> Self {
+        ClusteringReport {
+            triangles: stats.triangles.total,
+            average_clustering: stats.average_clustering,
+            transitivity: stats.transitivity,
+        }
+    }
+}
+
+/// Triangles and local clustering of one node, sorted by node.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClusteringRow {
+    pub node: usize,
+    pub triangles: usize,
+    pub clustering: f64,
+}
+
+/// Flatten per-node triangle counts and coefficients into rows.
+pub fn clustering_rows(stats: &ClusteringStats) -> Vec<ClusteringRow> {
+    let mut rows: Vec<_> = stats
+        .local_clustering
+        .iter()
+        .map(|(&node, &clustering)| ClusteringRow {
+            node,
+            triangles: stats.triangles.per_node[&node],
+            clustering,
+        })
+        .collect();
+    rows.sort_by_key(|r| r.node);
+    rows
+}
+
+/// One point of the C(k) curve (`clustering_by_degree.csv`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DegreeClusteringRow {
+    pub degree: usize,
+    pub clustering: f64,
+}
+
+/// Flatten C(k) into rows sorted by degree.
+pub fn degree_clustering_rows(stats: &ClusteringStats) -> Vec<DegreeClusteringRow> {
+    let mut rows: Vec<_> = stats
+        .clustering_by_degree
+        .iter()
+        .map(|(&degree, &clustering)| DegreeClusteringRow { degree, clustering })
+        .collect();
+    rows.sort_by_key(|r| r.degree);
+    rows
+}
+
+/// One row of an edge ranking (`edge_betweenness.csv`, `rank` starts at 1).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankedEdge {
+    pub rank: usize,
+    pub u: usize,
+    pub v: usize,
+    pub score: f64,
+}
+
+/// Rank a `(u, v) → score` map by descending score, ties by `(u, v)`.
+pub fn rank_edges(scores: &HashMap<(usize, usize), f64>) -> Vec<RankedEdge> {
+    let mut pairs: Vec<_> = scores.iter().map(|(&e, &s)| (e, s)).collect();
+    pairs.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    pairs
+        .into_iter()
+        .enumerate()
+        .map(|(i, ((u, v), score))| RankedEdge { rank: i + 1, u, v, score })
+        .collect()
+}
+
+/// Girvan–Newman summary (`girvan_newman.json`, without the per-split node
+/// lists); the best partition goes to `girvan_newman.csv` as [`CommunityRow`]s.
+#[derive(Debug, Clone, Serialize)]
+pub struct GirvanNewmanReport {
+    pub initial_communities: usize,
+    pub initial_modularity: f64,
+    pub splits: Vec<SplitRow>,
+    pub best_level: usize,
+    pub best_modularity: f64,
+}
+
+/// One dendrogram split without its node list.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SplitRow {
+    pub edges_removed: usize,
+    pub parent: usize,
+    pub child: usize,
+    pub moved: usize,
+    pub communities: usize,
+    pub modularity: f64,
+}
+
+impl From<&GirvanNewmanResult> for GirvanNewmanReport {
+    fn from(gn: &GirvanNewmanResult) -> Self {
+        let mut initial: Vec<usize> = gn.initial.values().copied().collect();
+        initial.sort_unstable();
+        initial.dedup();
+        GirvanNewmanReport {
+            initial_communities: initial.len(),
+            initial_modularity: gn.initial_modularity,
+            splits: gn
+                .splits
+                .iter()
+                .map(|s| SplitRow {
+                    edges_removed: s.edges_removed,
+                    parent: s.parent,
+                    child: s.child,
+                    moved: s.moved.len(),
+                    communities: s.communities,
+                    modularity: s.modularity,
+                })
+                .collect(),
+            best_level: gn.best_level,
+            best_modularity: gn.best_modularity,
+        }
+    }
+}
+
+/// Community of one node, sorted by node.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommunityRow {
+    pub node: usize,
+    pub community: usize,
+}
+
+/// Flatten a `node → community` partition into rows sorted by node.
+pub fn community_rows(partition: &HashMap<usize, usize>) -> Vec<CommunityRow> {
+    let mut rows: Vec<_> = partition
+        .iter()
+        .map(|(&node, &community)| CommunityRow { node, community })
+        .collect();
+    rows.sort_by_key(|r| r.node);
+    rows
+}
+
+/// Per-ego summary of the ego-Facebook dataset (`ego_networks.*`).
+#[derive(Debug, Clone, Serialize)]
+pub struct EgoSummaryRow {
+    pub ego: usize,
+    pub alters: usize,
+    pub circles: usize,
+    pub largest_circle: usize,
+    pub features: usize,
+}
+
+impl From<&EgoNetwork> for EgoSummaryRow {
+    fn from(net: &EgoNetwork) -> Self {
+        EgoSummaryRow {
+            ego: net.ego,
+            alters: net.alters.len(),
+            circles: net.circles.len(),
+            largest_circle: net.circles.iter().map(|c| c.members.len()).max().unwrap_or(0),
+            features: net.feature_names.len(),
+        }
+    }
+}
+
+/// Top-level description of one run (`manifest.json`).
+#[derive(Debug, Clone, Serialize)]
+pub struct Manifest {
+    pub schema_version: u32,
+    pub tool_version: String,
+    pub input: String,
+    pub nodes: usize,
+    pub edges: usize,
+    pub analyses: Vec<ManifestEntry>,
+}
+
+/// One analysis recorded in the manifest.
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub elapsed_secs: f64,
+    pub files: Vec<String>,
+}
+
+/// Writes result files into an output directory and keeps the manifest
+/// up to date; call [`RunWriter::finish`] to write `manifest.json`.
+pub struct RunWriter {
+    dir: PathBuf,
+    manifest: Manifest,
+}
+
+impl RunWriter {
+    /// Create (if needed) the output directory `dir` for a run on `input`.
+    pub fn create(
+        dir: impl AsRef<Path>,
+        input: &str,
+        nodes: usize,
+        edges: usize,
+    ) -> Result<Self, Box<dyn Error>> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(RunWriter {
+            dir,
+            manifest: Manifest {
+                schema_version: SCHEMA_VERSION,
+                tool_version: env!("CARGO_PKG_VERSION").to_string(),
+                input: input.to_string(),
+                nodes,
+                edges,
+                analyses: Vec::new(),
+            },
+        })
+    }
+
+    /// Manifest collected so far.
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Record a scalar / nested result as `<name>.json`.
+    pub fn record_json<T: Serialize + ?Sized>(
+        &mut self,
+        name: &str,
+        value: &T,
+        elapsed_secs: f64,
+    ) -> Result<(), Box<dyn Error>> {
+        let json = self.write_json(name, value)?;
+        self.push_entry(name, elapsed_secs, vec![json]);
+        Ok(())
+    }
+
+    /// Record a tabular result as both `<name>.json` (the full `value`)
+    /// and `<name>.csv` (one line per element of `rows`).
+    pub fn record_table<T: Serialize + ?Sized, R: Serialize>(
+        &mut self,
+        name: &str,
+        value: &T,
+        rows: &[R],
+        elapsed_secs: f64,
+    ) -> Result<(), Box<dyn Error>> {
+        let json = self.write_json(name, value)?;
+        let csv = self.write_csv(name, rows)?;
+        self.push_entry(name, elapsed_secs, vec![json, csv]);
+        Ok(())
+    }
+
+    /// Write `manifest.json` and return its path.
+    pub fn finish(self) -> Result<PathBuf, Box<dyn Error>> {
+        let path = self.dir.join("manifest.json");
+        let file = BufWriter::new(File::create(&path)?);
+        serde_json::to_writer_pretty(file, &self.manifest)?;
+        Ok(path)
+    }
+
+    fn write_json<T: Serialize + ?Sized>(
+        &self,
+        name: &str,
+        value: &T,
+    ) -> Result<String, Box<dyn Error>> {
+        let file_name = format!("{}.json", name);
+        let file = BufWriter::new(File::create(self.dir.join(&file_name))?);
+        serde_json::to_writer_pretty(file, value)?;
+        Ok(file_name)
+    }
+
+    fn write_csv<R: Serialize>(&self, name: &str, rows: &[R]) -> Result<String, Box<dyn Error>> {
+        let file_name = format!("{}.csv", name);
+        let mut writer = csv::Writer::from_path(self.dir.join(&file_name))?;
+        for row in rows {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+        Ok(file_name)
+    }
+
+    fn push_entry(&mut self, name: &str, elapsed_secs: f64, files: Vec<String>) {
+        self.manifest.analyses.push(ManifestEntry {
+            name: name.to_string(),
+            elapsed_secs,
+            files,
+        });
+    }
+}
@@ -1,84 +1,1112 @@
-//! Module `stats`: weighted least‐squares power‐law exponent estimation.
+//! Module `stats`: descriptive statistics of histograms, and power-law
+//! fitting of degree histograms by maximum likelihood with KS-based k_min
+//! selection, a bootstrap goodness-of-fit test and likelihood-ratio
+//! comparisons against alternative distributions (Clauset, Shalizi &
+//! Newman 2009).
 
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rayon::prelude::*;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::fmt;
 
-/// Estimate power‐law exponent α by weighted least‐squares fit
-/// of log(count_k) vs log(k), weighting each point by its count.
-/// That is, we solve for slope in
-///     y = a + b x,   b = –α
-/// by minimizing Σ wᵢ (yᵢ – a – b xᵢ)².
+/// A `value → count` histogram (e.g. from `degree_distribution` or
+/// `two_hop_distribution`) with descriptive statistics over its
+/// observations. Every statistic treats the histogram as the whole
+/// population: each node is one observation of its value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// (value, count) sorted by value, counts > 0
+    bins: Vec<(usize, usize)>,
+    /// Total number of observations
+    n: usize,
+}
+
+/// One CCDF point: the fraction of observations ≥ `value`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CcdfPoint {
+    /// A value present in the histogram
+    pub value: usize,
+    /// P(X ≥ value)
+    pub ccdf: f64,
+}
+
+/// One logarithmic bin covering the integers `lower..upper`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogBin {
+    /// Inclusive
+    pub lower: usize,
+    /// Exclusive
+    pub upper: usize,
+    /// Observations with lower ≤ value < upper
+    pub count: usize,
+    /// count / (n · number of integers in the bin): comparable to p(k)
+    pub density: f64,
+}
+
+/// The q-th quantile of a histogram.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuantilePoint {
+    /// Probability in [0, 1]
+    pub q: f64,
+    /// [`Histogram::quantile`] at `q`
+    pub value: f64,
+}
+
+/// Serializable snapshot of [`Histogram`]'s statistics.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistogramSummary {
+    /// Number of observations n
+    pub count: usize,
+    /// Number of distinct values
+    pub distinct: usize,
+    /// Smallest value observed
+    pub min: usize,
+    /// Largest value observed
+    pub max: usize,
+    /// Population mean
+    pub mean: f64,
+    /// Population variance
+    pub variance: f64,
+    /// √variance
+    pub std_dev: f64,
+    /// Median (type-7 quantile at 0.5)
+    pub median: f64,
+    /// At [`Histogram::SUMMARY_QUANTILES`]
+    pub quantiles: Vec<QuantilePoint>,
+    /// Most frequent value (smallest on ties)
+    pub mode: usize,
+    /// Population skewness m₃ / m₂^(3/2)
+    pub skewness: f64,
+    /// m₄ / m₂² − 3
+    pub excess_kurtosis: f64,
+    /// Gini coefficient of the values
+    pub gini: f64,
+    /// Shannon entropy of the value distribution, in bits
+    pub entropy_bits: f64,
+}
+
+impl Histogram {
+    /// Quantiles reported by [`Histogram::summary`].
+    pub const SUMMARY_QUANTILES: [f64; 7] = [0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99];
+
+    /// Builds the histogram, dropping zero counts; `None` if nothing is left.
+    pub fn new(counts: &HashMap<usize, usize>) -> Option<Self> {
+        let mut bins: Vec<(usize, usize)> = counts
+            .iter()
+            .filter(|&(_, &cnt)| cnt > 0)
+            .map(|(&k, &cnt)| (k, cnt))
+            .collect();
+        bins.sort_unstable();
+        let n = bins.iter().map(|&(_, cnt)| cnt).sum();
+        (n > 0).then_some(Histogram { bins, n })
+    }
+
+    /// Number of observations.
+    pub fn count(&self) -> usize {
+        self.n
+    }
+
+    /// Smallest value observed.
+    pub fn min(&self) -> usize {
+        self.bins[0].0
+    }
+
+    /// Largest value observed.
+    pub fn max(&self) -> usize {
+        self.bins[self.bins.len() - 1].0
+    }
+
+    /// Arithmetic mean Σ count·value / n.
+    pub fn mean(&self) -> f64 {
+        self.central_moment(1, 0.0)
+    }
+
+    /// Population variance (divides by n).
+    pub fn variance(&self) -> f64 {
+        self.central_moment(2, self.mean())
+    }
+
+    /// Population skewness m₃ / m₂^(3/2); 0 when every value is equal.
+    pub fn skewness(&self) -> f64 {
+        let (m2, m3) = (self.variance(), self.central_moment(3, self.mean()));
+        if m2 > 0.0 { m3 / m2.powf(1.5) } else { 0.0 }
+    }
+
+    /// Excess kurtosis m₄ / m₂² − 3 (0 for a normal distribution; 0 when
+    /// every value is equal).
+    pub fn excess_kurtosis(&self) -> f64 {
+        let (m2, m4) = (self.variance(), self.central_moment(4, self.mean()));
+        if m2 > 0.0 { m4 / (m2 * m2) - 3.0 } else { 0.0 }
+    }
+
+    /// Σ count·(value − center)^p / n
+    fn central_moment(&self, p: i32, center: f64) -> f64 {
+        let sum: f64 = self
+            .bins
+            .iter()
+            .map(|&(k, cnt)| cnt as f64 * (k as f64 - center).powi(p))
+            .sum();
+        sum / self.n as f64
+    }
+
+    /// Quantile `q` ∈ [0, 1] by linear interpolation between order
+    /// statistics (Hyndman–Fan type 7, the R and NumPy default): with the
+    /// observations sorted as x₀ ≤ … ≤ x_{n−1} and h = (n − 1)·q, it is
+    /// x_⌊h⌋ + (h − ⌊h⌋)·(x_⌊h⌋₊₁ − x_⌊h⌋).
+    pub fn quantile(&self, q: f64) -> f64 {
+        let h = (self.n - 1) as f64 * q.clamp(0.0, 1.0);
+        let lo = h.floor() as usize;
+        let x_lo = self.order_statistic(lo) as f64;
+        if lo + 1 >= self.n {
+            return x_lo;
+        }
+        x_lo + (h - lo as f64) * (self.order_statistic(lo + 1) as f64 - x_lo)
+    }
+
+    /// The 0.5 quantile.
+    pub fn median(&self) -> f64 {
+        self.quantile(0.5)
+    }
+
+    /// The `i`-th smallest observation (0-based).
+    fn order_statistic(&self, i: usize) -> usize {
+        let mut seen = 0;
+        for &(k, cnt) in &self.bins {
+            seen += cnt;
+            if i < seen {
+                return k;
+            }
+        }
+        self.max()
+    }
+
+    /// Most frequent value; ties go to the smallest.
+    pub fn mode(&self) -> usize {
+        let mut best = self.bins[0];
+        for &(k, cnt) in &self.bins[1..] {
+            if cnt > best.1 {
+                best = (k, cnt);
+            }
+        }
+        best.0
+    }
+
+    /// Gini coefficient of the values: 0 when all are equal, → 1 when one
+    /// observation holds everything; 0 if they sum to 0.
+    ///
+    /// With the observations ranked 1..n in ascending order,
+    /// G = 2·Σ i·xᵢ / (n·Σ xᵢ) − (n + 1)/n; a value with count c occupying
+    /// ranks r+1..r+c contributes (c·r + c(c+1)/2)·value.
+    pub fn gini(&self) -> f64 {
+        let total: f64 = self.bins.iter().map(|&(k, cnt)| (k * cnt) as f64).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        let (mut rank, mut weighted) = (0.0, 0.0);
+        for &(k, cnt) in &self.bins {
+            let c = cnt as f64;
+            weighted += (c * rank + c * (c + 1.0) / 2.0) * k as f64;
+            rank += c;
+        }
+        let n = self.n as f64;
+        2.0 * weighted / (n * total) - (n + 1.0) / n
+    }
+
+    /// Shannon entropy −Σ p·log₂ p of the value distribution, in bits.
+    pub fn entropy_bits(&self) -> f64 {
+        let n = self.n as f64;
+        -self
+            .bins
+            .iter()
+            .map(|&(_, cnt)| {
+                let p = cnt as f64 / n;
+                p * p.log2()
+            })
+            .sum::<f64>()
+    }
+
+    /// P(X ≥ value) at every distinct value, ascending; starts at 1.
+    pub fn ccdf(&self) -> Vec<CcdfPoint> {
+        let mut at_least = self.n;
+        self.bins
+            .iter()
+            .map(|&(value, cnt)| {
+                let point = CcdfPoint { value, ccdf: at_least as f64 / self.n as f64 };
+                at_least -= cnt;
+                point
+            })
+            .collect()
+    }
+
+    /// Logarithmic bins with `per_decade` bins per factor of 10, for plotting
+    /// p(k) on log–log axes. Real edges 10^(i/per_decade) are rounded up to
+    /// integers; bins with no integer inside or no observations are
+    /// skipped, and so is the value 0, which has no place on a log axis.
+    /// Densities divide by all n observations, zeros included.
+    pub fn log_bins(&self, per_decade: usize) -> Vec<LogBin> {
+        let ratio = 10f64.powf(1.0 / per_decade.max(1) as f64);
+        let mut bins = Vec::new();
+        let mut values = self.bins.iter().filter(|&&(k, _)| k > 0).peekable();
+        let (mut i, mut lower) = (0, 1);
+        while values.peek().is_some() {
+            i += 1;
+            let upper = (ratio.powi(i) - 1e-9).ceil() as usize;
+            if upper <= lower {
+                continue;
+            }
+            let mut count = 0;
+            while let Some(&(_, cnt)) = values.next_if(|&&(k, _)| k < upper) {
+                count += cnt;
+            }
+            if count > 0 {
+                let density = count as f64 / (self.n * (upper - lower)) as f64;
+                bins.push(LogBin { lower, upper, count, density });
+            }
+            lower = upper;
+        }
+        bins
+    }
+
+    /// Every statistic above in one serializable value.
+    pub fn summary(&self) -> HistogramSummary {
+        let variance = self.variance();
+        HistogramSummary {
+            count: self.n,
+            distinct: self.bins.len(),
+            min: self.min(),
+            max: self.max(),
+            mean: self.mean(),
+            variance,
+            std_dev: variance.sqrt(),
+            median: self.median(),
+            quantiles: Self::SUMMARY_QUANTILES
+                .iter()
+                .map(|&q| QuantilePoint { q, value: self.quantile(q) })
+                .collect(),
+            mode: self.mode(),
+            skewness: self.skewness(),
+            excess_kurtosis: self.excess_kurtosis(),
+            gini: self.gini(),
+            entropy_bits: self.entropy_bits(),
+        }
+    }
+}
+
+/// Which likelihood [`fit_power_law`] maximizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PowerLawMethod {
+    /// Exact discrete power law p(k) = k^(−α) / ζ(α, k_min), maximized numerically.
+    Discrete,
+    /// Closed-form continuous approximation with k_min shifted by ½ (CSN eq. 3.7).
+    ContinuousApprox,
+}
+
+/// Maximum-likelihood power-law fit to the tail k ≥ `k_min` of a histogram.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PowerLawFit {
+    /// Estimator used
+    pub method: PowerLawMethod,
+    /// Inclusive lower bound of the fitted tail
+    pub k_min: usize,
+    /// Number of observations with k ≥ k_min
+    pub tail_size: usize,
+    /// Estimated exponent α̂
+    pub alpha: f64,
+    /// Asymptotic standard error of α̂ (inverse Fisher information)
+    pub std_error: f64,
+    /// Discrete log-likelihood of the tail at α̂ (for either method)
+    pub log_likelihood: f64,
+    /// Kolmogorov–Smirnov distance between the empirical and fitted tail CCDFs
+    pub ks_distance: f64,
+}
+
+/// Fit p(k) ∝ k^(−α) to the observations k ≥ `k_min` of a histogram.
+///
+/// # Inputs
+/// - `degree_counts`: map degree k → count of nodes with that degree
+/// - `k_min`: inclusive lower bound of the power-law tail (≥ 1)
+/// - `method`: exact discrete MLE or its continuous approximation
+///
+/// # Output
+/// - `Some(PowerLawFit)`, or `None` if `k_min` is 0, or the tail has fewer
+///   than two observations or a single distinct value (α̂ would be infinite)
+///
+/// # Logic
+/// With n tail observations and S = Σ ln kᵢ, the discrete log-likelihood is
+/// L(α) = −n·ln ζ(α, k_min) − α·S, where ζ is the Hurwitz zeta function.
+/// L is concave in α, so `Discrete` maximizes it by golden-section search
+/// on (1, 50], and the standard error is 1/√(n·(ln ζ)''(α̂)), the second
+/// derivative taken by central differences. `ContinuousApprox` uses
+/// α̂ = 1 + n / Σ ln(kᵢ / (k_min − ½)) with standard error (α̂ − 1)/√n.
+/// The KS distance compares P(K ≥ k) of the tail with the discrete model
+/// at α̂ over every integer k_min ≤ k ≤ max k + 1.
+pub fn fit_power_law(
+    degree_counts: &HashMap<usize, usize>,
+    k_min: usize,
+    method: PowerLawMethod,
+) -> Option<PowerLawFit> {
+    if k_min == 0 {
+        return None;
+    }
+    let mut tail: Vec<(usize, usize)> = degree_counts
+        .iter()
+        .filter(|&(&k, &cnt)| k >= k_min && cnt > 0)
+        .map(|(&k, &cnt)| (k, cnt))
+        .collect();
+    tail.sort_unstable();
+    let n: usize = tail.iter().map(|&(_, cnt)| cnt).sum();
+    if n < 2 || tail.len() < 2 {
+        return None;
+    }
+    let sum_ln: f64 = tail.iter().map(|&(k, cnt)| cnt as f64 * (k as f64).ln()).sum();
+
+    let q = k_min as f64;
+    let nf = n as f64;
+    let log_likelihood = |alpha: f64| -nf * hurwitz_zeta(alpha, q).ln() - alpha * sum_ln;
+    let (alpha, std_error) = match method {
+        PowerLawMethod::Discrete => {
+            let alpha = golden_section_max(log_likelihood, 1.0 + 1e-9, 50.0, 1e-10);
+            let h = 1e-4;
+            let ln_zeta = |a: f64| hurwitz_zeta(a, q).ln();
+            let curvature =
+                (ln_zeta(alpha + h) - 2.0 * ln_zeta(alpha) + ln_zeta(alpha - h)) / (h * h);
+            (alpha, 1.0 / (nf * curvature).sqrt())
+        }
+        PowerLawMethod::ContinuousApprox => {
+            let alpha = 1.0 + nf / (sum_ln - nf * (q - 0.5).ln());
+            (alpha, (alpha - 1.0) / nf.sqrt())
+        }
+    };
+
+    Some(PowerLawFit {
+        method,
+        k_min,
+        tail_size: n,
+        alpha,
+        std_error,
+        log_likelihood: log_likelihood(alpha),
+        ks_distance: ks_distance(&tail, n, alpha),
+    })
+}
+
+/// Power-law fit with k_min chosen by Kolmogorov–Smirnov minimization
+/// (Clauset–Shalizi–Newman).
+///
+/// # Inputs
+/// - `degree_counts`: map degree k → count of nodes with that degree
+/// - `method`: likelihood used to fit α at each candidate
+///
+/// # Output
+/// - The [`PowerLawFit`] with the smallest `ks_distance`, or `None` if no
+///   candidate can be fitted
+///
+/// # Logic
+/// Every distinct positive degree is a candidate k_min; [`fit_power_law`]
+/// fits α and the KS distance of the tail above it. Ties go to the smaller
+/// k_min, which keeps more of the data. O(candidates · (max k + fit)).
+pub fn fit_power_law_tail(
+    degree_counts: &HashMap<usize, usize>,
+    method: PowerLawMethod,
+) -> Option<PowerLawFit> {
+    let mut candidates: Vec<usize> = degree_counts
+        .iter()
+        .filter(|&(&k, &cnt)| k > 0 && cnt > 0)
+        .map(|(&k, _)| k)
+        .collect();
+    candidates.sort_unstable();
+    candidates
+        .into_iter()
+        .filter_map(|k_min| fit_power_law(degree_counts, k_min, method))
+        .min_by(|a, b| a.ks_distance.total_cmp(&b.ks_distance))
+}
+
+/// max_k |P̂(K ≥ k) − P(K ≥ k)| for the sorted `tail` of `n` observations
+/// against the discrete power law with exponent `alpha` from its smallest k.
+///
+/// Between observed values the empirical CCDF is flat and the model's falls,
+/// so the maximum is attained at some observed kᵢ or at kᵢ + 1; only those
+/// points are evaluated, with P(K ≥ k) = ζ(α, k)/ζ(α, k_min).
+fn ks_distance(tail: &[(usize, usize)], n: usize, alpha: f64) -> f64 {
+    let zeta = hurwitz_zeta(alpha, tail[0].0 as f64);
+    let mut at_least = n;
+    let mut distance: f64 = 0.0;
+    for &(k, cnt) in tail {
+        let empirical = at_least as f64 / n as f64;
+        distance = distance.max((empirical - hurwitz_zeta(alpha, k as f64) / zeta).abs());
+        at_least -= cnt;
+        let empirical = at_least as f64 / n as f64;
+        distance = distance.max((empirical - hurwitz_zeta(alpha, k as f64 + 1.0) / zeta).abs());
+    }
+    distance
+}
+
+/// Discrete maximum-likelihood power-law exponent (see [`fit_power_law`]).
 ///
 /// # Inputs
 /// - `degree_counts`: map degree k → count of nodes with that degree
 /// - `k_min`: inclusive lower bound for degrees to include in the fit
 ///
 /// # Returns
-/// - Estimated α̂ (>0), or 0 if insufficient data
+/// - Estimated α̂ (>1), or 0 if insufficient data
 pub fn mle_power_law_exponent(
     degree_counts: &HashMap<usize, usize>,
     k_min: usize,
 ) -> f64 {
-    // First pass: accumulate weighted sums for means
-    let mut sum_w = 0.0;
-    let mut sum_wx = 0.0;
-    let mut sum_wy = 0.0;
+    fit_power_law(degree_counts, k_min, PowerLawMethod::Discrete).map_or(0.0, |fit| fit.alpha)
+}
 
-    for (&k, &cnt) in degree_counts {
-        if k >= k_min && cnt > 0 {
-            let w = cnt as f64;
-            let x = (k as f64).ln();
-            let y = (cnt as f64).ln();
-            sum_w += w;
-            sum_wx += w * x;
-            sum_wy += w * y;
+/// Options for [`power_law_gof`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GoodnessOfFitOptions {
+    /// Number of synthetic datasets; p is accurate to about ±1/(2√replicates)
+    pub replicates: usize,
+    pub seed: u64,
+    /// Re-select k_min on every synthetic dataset, as [`fit_power_law_tail`]
+    /// did on the data; otherwise keep the fit's k_min
+    pub select_k_min: bool,
+}
+
+/// Result of the bootstrap goodness-of-fit test.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoodnessOfFit {
+    /// Fraction of synthetic datasets fitting worse than the data; a power
+    /// law is ruled out when this is small (CSN suggest p ≤ 0.1)
+    pub p_value: f64,
+    /// KS distance of the fit being tested
+    pub observed_ks: f64,
+    /// Synthetic datasets that could be refitted
+    pub replicates: usize,
+    /// Of those, how many had a KS distance ≥ `observed_ks`
+    pub exceeding: usize,
+}
+
+/// Semi-parametric bootstrap test of whether `fit` is a plausible model
+/// for `degree_counts` (Clauset–Shalizi–Newman §4.1).
+///
+/// # Inputs
+/// - `degree_counts`: the histogram `fit` was estimated from
+/// - `fit`: the power-law fit to test
+/// - `opts`: replicates, seed and whether k_min is re-selected
+///
+/// # Output
+/// - [`GoodnessOfFit`] with the p-value and the counts behind it
+///
+/// # Logic
+/// Each synthetic dataset has as many observations as the data: each one
+/// is, with probability n_tail/n, a draw from the fitted discrete power law
+/// (inverse CDF, tabulated for 10⁵ values above k_min, continuous
+/// approximation beyond) and otherwise a uniform draw from the observations
+/// below k_min. It is refitted the same way as the data and its KS distance
+/// compared with `fit.ks_distance`. Per-replicate seeds are drawn up front
+/// and replicates run in parallel, so results depend only on `seed`.
+pub fn power_law_gof(
+    degree_counts: &HashMap<usize, usize>,
+    fit: &PowerLawFit,
+    opts: &GoodnessOfFitOptions,
+) -> GoodnessOfFit {
+    let mut bulk: Vec<(usize, usize)> = degree_counts
+        .iter()
+        .filter(|&(&k, &cnt)| k < fit.k_min && cnt > 0)
+        .map(|(&k, &cnt)| (k, cnt))
+        .collect();
+    bulk.sort_unstable();
+    let bulk_values: Vec<usize> = bulk.iter().map(|&(k, _)| k).collect();
+    let bulk_cumulative: Vec<usize> = bulk
+        .iter()
+        .scan(0, |acc, &(_, cnt)| {
+            *acc += cnt;
+            Some(*acc)
+        })
+        .collect();
+    let bulk_size = bulk_cumulative.last().copied().unwrap_or(0);
+    let n = bulk_size + fit.tail_size;
+    let tail_share = fit.tail_size as f64 / n as f64;
+    let sampler = PowerLawSampler::new(fit.alpha, fit.k_min);
+
+    let mut rng = StdRng::seed_from_u64(opts.seed);
+    let seeds: Vec<u64> = (0..opts.replicates).map(|_| rng.random()).collect();
+    let synthetic_ks: Vec<f64> = seeds
+        .into_par_iter()
+        .filter_map(|seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut counts = HashMap::new();
+            for _ in 0..n {
+                let k = if rng.random::<f64>() < tail_share {
+                    sampler.sample(&mut rng)
+                } else {
+                    let r = rng.random_range(0..bulk_size);
+                    bulk_values[bulk_cumulative.partition_point(|&c| c <= r)]
+                };
+                *counts.entry(k).or_insert(0) += 1;
+            }
+            let refit = if opts.select_k_min {
+                fit_power_law_tail(&counts, fit.method)
+            } else {
+                fit_power_law(&counts, fit.k_min, fit.method)
+            };
+            refit.map(|f| f.ks_distance)
+        })
+        .collect();
+
+    let exceeding = synthetic_ks.iter().filter(|&&d| d >= fit.ks_distance).count();
+    GoodnessOfFit {
+        p_value: exceeding as f64 / synthetic_ks.len().max(1) as f64,
+        observed_ks: fit.ks_distance,
+        replicates: synthetic_ks.len(),
+        exceeding,
+    }
+}
+
+/// Inverse-CDF sampler for the discrete power law k^(−α)/ζ(α, k_min).
+struct PowerLawSampler {
+    alpha: f64,
+    k_min: usize,
+    /// P(K ≤ k_min + i)
+    cdf: Vec<f64>,
+}
+
+impl PowerLawSampler {
+    const TABLE: usize = 100_000;
+
+    fn new(alpha: f64, k_min: usize) -> Self {
+        let zeta = hurwitz_zeta(alpha, k_min as f64);
+        let cdf = (k_min..k_min + Self::TABLE)
+            .scan(0.0, |acc, k| {
+                *acc += (k as f64).powf(-alpha) / zeta;
+                Some(*acc)
+            })
+            .collect();
+        PowerLawSampler { alpha, k_min, cdf }
+    }
+
+    /// Beyond the table, k is drawn from the continuous approximation
+    /// conditioned on k ≥ k_min + TABLE (saturating at `usize::MAX`).
+    fn sample(&self, rng: &mut impl Rng) -> usize {
+        let u: f64 = rng.random();
+        let covered = self.cdf[self.cdf.len() - 1];
+        if u < covered {
+            return self.k_min + self.cdf.partition_point(|&c| c <= u);
         }
+        let v = ((u - covered) / (1.0 - covered)).min(1.0 - f64::EPSILON);
+        let start = (self.k_min + Self::TABLE) as f64 - 0.5;
+        (start * (1.0 - v).powf(-1.0 / (self.alpha - 1.0)) + 0.5) as usize
     }
+}
 
-    if sum_w == 0.0 {
-        return 0.0;
+/// A parametric distribution on the tail k ≥ k_min, fitted as an
+/// alternative to the power law. Every model is discrete and normalized
+/// over k ≥ k_min; the continuous ones are binned, so that
+/// p(k) = P(k − ½ < X ≤ k + ½) / P(X > k_min − ½).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum TailModel {
+    /// Binned lognormal: ln X ~ N(μ, σ²)
+    Lognormal { mu: f64, sigma: f64 },
+    /// Geometric decay p(k) ∝ e^(−λk), i.e. the binned exponential
+    Exponential { lambda: f64 },
+    /// Binned stretched exponential (Weibull): P(X > x) = e^(−λ·x^β)
+    StretchedExponential { lambda: f64, beta: f64 },
+    /// Poisson(μ) truncated to k ≥ k_min
+    Poisson { mu: f64 },
+    /// p(k) ∝ k^(−α)·e^(−λk), which reduces to the power law at λ = 0
+    PowerLawCutoff { alpha: f64, lambda: f64 },
+}
+
+impl fmt::Display for TailModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TailModel::Lognormal { mu, sigma } => write!(f, "lognormal (μ = {:.4}, σ = {:.4})", mu, sigma),
+            TailModel::Exponential { lambda } => write!(f, "exponential (λ = {:.4e})", lambda),
+            TailModel::StretchedExponential { lambda, beta } => {
+                write!(f, "stretched exponential (λ = {:.4e}, β = {:.4})", lambda, beta)
+            }
+            TailModel::Poisson { mu } => write!(f, "Poisson (μ = {:.4})", mu),
+            TailModel::PowerLawCutoff { alpha, lambda } => {
+                write!(f, "power law with cutoff (α = {:.4}, λ = {:.4e})", alpha, lambda)
+            }
+        }
     }
+}
 
-    let mean_x = sum_wx / sum_w;
-    let mean_y = sum_wy / sum_w;
+/// The alternatives [`compare_to_power_law`] can fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Alternative {
+    Lognormal,
+    Exponential,
+    StretchedExponential,
+    Poisson,
+    PowerLawCutoff,
+}
+
+impl Alternative {
+    pub const ALL: [Alternative; 5] = [
+        Alternative::Lognormal,
+        Alternative::Exponential,
+        Alternative::StretchedExponential,
+        Alternative::Poisson,
+        Alternative::PowerLawCutoff,
+    ];
+}
 
-    // Second pass: compute weighted covariance and variance
-    let mut cov_xy = 0.0;
-    let mut var_x = 0.0;
-    for (&k, &cnt) in degree_counts {
-        if k >= k_min && cnt > 0 {
-            let w = cnt as f64;
-            let x = (k as f64).ln();
-            let y = (cnt as f64).ln();
-            let dx = x - mean_x;
-            let dy = y - mean_y;
-            cov_xy += w * dx * dy;
-            var_x += w * dx * dx;
+/// Likelihood-ratio comparison of a power-law fit with one alternative.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LikelihoodRatio {
+    /// The alternative's maximum-likelihood fit on the same tail
+    pub model: TailModel,
+    pub log_likelihood: f64,
+    /// R = ℓ(power law) − ℓ(alternative); positive favours the power law
+    pub ratio: f64,
+    /// Vuong's R / (σ·√n), asymptotically N(0, 1) when neither model is true
+    pub normalized_ratio: f64,
+    /// Probability of a |R| this large by chance: two-sided Vuong test, or
+    /// the χ²₁ likelihood-ratio test for the nested `PowerLawCutoff`.
+    /// Only a small value makes the sign of R meaningful.
+    pub p_value: f64,
+}
+
+impl LikelihoodRatio {
+    /// Which model the data favour at significance level `level`, if either.
+    pub fn favoured(&self, level: f64) -> Option<&'static str> {
+        if self.p_value >= level {
+            None
+        } else if self.ratio > 0.0 {
+            Some("power law")
+        } else {
+            Some("alternative")
+        }
+    }
+}
+
+/// Fit `alternative` to the tail k ≥ `fit.k_min` of `degree_counts` and
+/// compare it with the power law `fit` by likelihood ratio.
+///
+/// # Inputs
+/// - `degree_counts`: any histogram value → count, e.g. from
+///   `degree_distribution` or `two_hop_distribution`
+/// - `fit`: the power-law fit to compare against (from [`fit_power_law`]
+///   or [`fit_power_law_tail`]); its k_min defines the tail
+/// - `alternative`: which distribution to fit
+///
+/// # Output
+/// - [`LikelihoodRatio`], or `None` if the tail is degenerate or the
+///   alternative's likelihood cannot be evaluated
+///
+/// # Logic
+/// The exponential has a closed-form MLE; the truncated Poisson's
+/// log-likelihood is concave in ln μ and maximized by golden-section search;
+/// the two-parameter models by Nelder–Mead on unconstrained parameters
+/// (log scale for positive ones), restarted once from the optimum. With the
+/// pointwise differences dᵢ = ln p_power(kᵢ) − ln p_alt(kᵢ) (the power law
+/// with its discrete pmf at α̂), R = Σ dᵢ and σ² is the variance of the dᵢ;
+/// Vuong's p is erfc(|R| / (σ·√(2n))). The power law with cutoff contains
+/// the power law, so there p = P(χ²₁ > 2|R|) = erfc(√|R|) instead.
+pub fn compare_to_power_law(
+    degree_counts: &HashMap<usize, usize>,
+    fit: &PowerLawFit,
+    alternative: Alternative,
+) -> Option<LikelihoodRatio> {
+    let k_min = fit.k_min;
+    let mut tail: Vec<(usize, usize)> = degree_counts
+        .iter()
+        .filter(|&(&k, &cnt)| k >= k_min && cnt > 0)
+        .map(|(&k, &cnt)| (k, cnt))
+        .collect();
+    tail.sort_unstable();
+    if k_min == 0 || tail.len() < 2 {
+        return None;
+    }
+
+    let model = fit_tail_model(&tail, k_min, alternative, fit.alpha)?;
+    let ln_zeta = hurwitz_zeta(fit.alpha, k_min as f64).ln();
+    let alt = tail_ln_pmf(&model, &tail, k_min);
+    // (dᵢ, count) per distinct k
+    let diffs: Vec<(f64, f64)> = tail
+        .iter()
+        .zip(&alt)
+        .map(|(&(k, cnt), a)| (-fit.alpha * (k as f64).ln() - ln_zeta - a, cnt as f64))
+        .collect();
+    let n: f64 = diffs.iter().map(|&(_, w)| w).sum();
+    let ratio: f64 = diffs.iter().map(|&(d, w)| w * d).sum();
+    let mean = ratio / n;
+    let variance = diffs.iter().map(|&(d, w)| w * (d - mean).powi(2)).sum::<f64>() / n;
+    let normalized_ratio = if variance > 0.0 { ratio / (variance * n).sqrt() } else { 0.0 };
+    let p_value = match alternative {
+        // the cutoff model nests the power law, so its likelihood is never lower
+        Alternative::PowerLawCutoff => erfc((-ratio).max(0.0).sqrt()),
+        _ => erfc(normalized_ratio.abs() / std::f64::consts::SQRT_2),
+    };
+    Some(LikelihoodRatio {
+        model,
+        log_likelihood: tail.iter().zip(&alt).map(|(&(_, cnt), a)| cnt as f64 * a).sum(),
+        ratio,
+        normalized_ratio,
+        p_value,
+    })
+}
+
+/// MLE of `alternative` on the sorted, non-empty `tail`; `alpha` (the
+/// power-law exponent) seeds the cutoff model.
+fn fit_tail_model(
+    tail: &[(usize, usize)],
+    k_min: usize,
+    alternative: Alternative,
+    alpha: f64,
+) -> Option<TailModel> {
+    let n: f64 = tail.iter().map(|&(_, cnt)| cnt as f64).sum();
+    let mean = |g: &dyn Fn(f64) -> f64| -> f64 {
+        tail.iter().map(|&(k, cnt)| cnt as f64 * g(k as f64)).sum::<f64>() / n
+    };
+    let excess = mean(&|k| k) - k_min as f64;
+    let exponential = (1.0 / excess).ln_1p();
+    let k_max = tail[tail.len() - 1].0 as f64;
+    let nll = |model: TailModel| -> f64 {
+        let ll: f64 = tail_ln_pmf(&model, tail, k_min)
+            .iter()
+            .zip(tail)
+            .map(|(lp, &(_, cnt))| cnt as f64 * lp)
+            .sum();
+        if ll.is_finite() { -ll } else { f64::INFINITY }
+    };
+
+    let model = match alternative {
+        Alternative::Exponential => TailModel::Exponential { lambda: exponential },
+        Alternative::Poisson => {
+            let ll = |theta: f64| -nll(TailModel::Poisson { mu: theta.exp() });
+            let theta = golden_section_max(ll, -10.0, (10.0 * k_max).ln(), 1e-10);
+            TailModel::Poisson { mu: theta.exp() }
+        }
+        Alternative::Lognormal => {
+            let mu = mean(&|k| k.ln());
+            let sd = (mean(&|k| k.ln().powi(2)) - mu * mu).max(1e-6).sqrt();
+            let p = nelder_mead(
+                |p| nll(TailModel::Lognormal { mu: p[0], sigma: p[1].exp() }),
+                &[mu, sd.ln()],
+            );
+            TailModel::Lognormal { mu: p[0], sigma: p[1].exp() }
+        }
+        Alternative::StretchedExponential => {
+            let p = nelder_mead(
+                |p| nll(TailModel::StretchedExponential { lambda: p[0].exp(), beta: p[1].exp() }),
+                &[exponential.ln(), 0.0],
+            );
+            TailModel::StretchedExponential { lambda: p[0].exp(), beta: p[1].exp() }
+        }
+        Alternative::PowerLawCutoff => {
+            let p = nelder_mead(
+                |p| nll(TailModel::PowerLawCutoff { alpha: p[0], lambda: p[1].exp() }),
+                &[alpha, (1e-3 / k_max).ln()],
+            );
+            TailModel::PowerLawCutoff { alpha: p[0], lambda: p[1].exp() }
+        }
+    };
+    nll(model).is_finite().then_some(model)
+}
+
+/// ln p(k) under `model` for each distinct k of `tail`.
+fn tail_ln_pmf(model: &TailModel, tail: &[(usize, usize)], k_min: usize) -> Vec<f64> {
+    let lower = k_min as f64 - 0.5;
+    match *model {
+        TailModel::Lognormal { mu, sigma } => {
+            let z = |x: f64| (x.ln() - mu) / sigma;
+            let norm = ln_normal_sf(z(lower));
+            tail.iter()
+                .map(|&(k, _)| ln_normal_interval(z(k as f64 - 0.5), z(k as f64 + 0.5)) - norm)
+                .collect()
+        }
+        TailModel::Exponential { lambda } => {
+            let norm = (-(-lambda).exp_m1()).ln();
+            tail.iter().map(|&(k, _)| norm - lambda * (k - k_min) as f64).collect()
+        }
+        TailModel::StretchedExponential { lambda, beta } => tail
+            .iter()
+            .map(|&(k, _)| {
+                let (a, b) = ((k as f64 - 0.5).powf(beta), (k as f64 + 0.5).powf(beta));
+                lambda * (lower.powf(beta) - a) + (-(-lambda * (b - a)).exp_m1()).ln()
+            })
+            .collect(),
+        TailModel::Poisson { mu } => {
+            let norm = ln_poisson_tail(mu, k_min);
+            tail.iter()
+                .map(|&(k, _)| -mu + k as f64 * mu.ln() - ln_gamma(k as f64 + 1.0) - norm)
+                .collect()
+        }
+        TailModel::PowerLawCutoff { alpha, lambda } => {
+            let norm = ln_cutoff_normalizer(alpha, lambda, k_min);
+            tail.iter()
+                .map(|&(k, _)| -alpha * (k as f64).ln() - lambda * (k - k_min) as f64 - norm)
+                .collect()
         }
     }
+}
 
-    if var_x == 0.0 {
+/// ln Σ_{j ≥ k_min} e^(−μ)·μ^j / j!, without underflow for k_min ≫ μ.
+fn ln_poisson_tail(mu: f64, k_min: usize) -> f64 {
+    let ln_term = |j: usize| -mu + j as f64 * mu.ln() - ln_gamma(j as f64 + 1.0);
+    if k_min == 0 {
         return 0.0;
     }
+    // sum the side of the mode that k_min is on; terms shrink away from μ
+    let (first, mut j, upward) =
+        if k_min as f64 > mu { (k_min, k_min, true) } else { (k_min - 1, k_min - 1, false) };
+    let (mut sum, mut term) = (1.0, 1.0);
+    loop {
+        term *= if upward { mu / (j + 1) as f64 } else { j as f64 / mu };
+        sum += term;
+        if term < 1e-17 * sum || (!upward && j == 0) {
+            break;
+        }
+        j = if upward { j + 1 } else { j - 1 };
+    }
+    let ln_side = ln_term(first) + sum.ln();
+    if upward { ln_side } else { (-ln_side.exp()).ln_1p() }
+}
+
+/// ln Σ_{j ≥ k_min} j^(−α)·e^(−λ(j − k_min)), for λ > 0.
+///
+/// Sums the first 200 terms directly; the rest is Euler–Maclaurin,
+/// ∫_N^∞ f + f(N)/2 − f'(N)/12, with the integral taken by Simpson's rule
+/// after substituting x = N·eᵗ.
+fn ln_cutoff_normalizer(alpha: f64, lambda: f64, k_min: usize) -> f64 {
+    const DIRECT: usize = 200;
+    if lambda <= 0.0 {
+        // underflowed to the plain power law, normalizable only for α > 1
+        return if alpha > 1.0 { hurwitz_zeta(alpha, k_min as f64).ln() } else { f64::INFINITY };
+    }
+    let f = |x: f64| x.powf(-alpha) * (-lambda * (x - k_min as f64)).exp();
+    let mut sum = 0.0;
+    for j in k_min..k_min + DIRECT {
+        let term = f(j as f64);
+        sum += term;
+        if term < 1e-17 * sum {
+            return sum.ln();
+        }
+    }
+    let big_n = (k_min + DIRECT) as f64;
+    let phi = |t: f64| (1.0 - alpha) * t - lambda * big_n * t.exp_m1();
+    // integrand N·f(N)·e^φ(t): walk past its peak until e^φ is negligible
+    let mut end = 1.0;
+    while end < 1e4 && (phi(end) > -40.0 || (1.0 - alpha) - lambda * big_n * end.exp() > 0.0) {
+        end *= 2.0;
+    }
+    let steps = ((end / 0.01).ceil() as usize).clamp(100, 100_000) & !1;
+    let h = end / steps as f64;
+    let simpson: f64 = (0..=steps)
+        .map(|i| {
+            let w = if i == 0 || i == steps { 1.0 } else if i % 2 == 1 { 4.0 } else { 2.0 };
+            w * phi(i as f64 * h).exp()
+        })
+        .sum::<f64>()
+        * h
+        / 3.0;
+    let f_n = f(big_n);
+    let derivative = f_n * (-alpha / big_n - lambda);
+    (sum + big_n * f_n * simpson + f_n / 2.0 - derivative / 12.0).ln()
+}
+
+/// ln P(Z > z) for a standard normal Z.
+fn ln_normal_sf(z: f64) -> f64 {
+    ln_erfc(z / std::f64::consts::SQRT_2) - std::f64::consts::LN_2
+}
+
+/// ln P(a < Z ≤ b) for a standard normal Z and a < b, evaluated in
+/// whichever tail keeps precision.
+fn ln_normal_interval(a: f64, b: f64) -> f64 {
+    if a > 0.0 {
+        let upper = ln_normal_sf(a);
+        upper + (-(ln_normal_sf(b) - upper).exp_m1()).ln()
+    } else if b < 0.0 {
+        ln_normal_interval(-b, -a)
+    } else {
+        (-(ln_normal_sf(b).exp() + ln_normal_sf(-a).exp())).ln_1p()
+    }
+}
+
+/// Complementary error function, accurate to ~1e-15 relative.
+fn erfc(x: f64) -> f64 {
+    if x < 0.0 { 2.0 - erfc(-x) } else { ln_erfc(x).exp() }
+}
 
-    // In y = a + b x, slope b = cov_xy / var_x, and α̂ = –b
-    - (cov_xy / var_x)
+/// ln erfc(x): 1 − erf(x) from its series for 0 ≤ x < 2.5, otherwise the
+/// continued fraction erfc(x) = e^(−x²)/√π · 1/(x + ½/(x + 1/(x + 3⁄2/(x + …)))).
+fn ln_erfc(x: f64) -> f64 {
+    use std::f64::consts::PI;
+    if x < 0.0 {
+        return (2.0 - ln_erfc(-x).exp()).ln();
+    }
+    if x < 2.5 {
+        // erf(x) = 2/√π·e^(−x²)·Σ 2ⁿx^(2n+1)/(1·3·…·(2n+1)), all terms positive
+        let (mut term, mut sum, mut n) = (x, x, 0.0);
+        while term > 1e-17 * sum {
+            n += 1.0;
+            term *= 2.0 * x * x / (2.0 * n + 1.0);
+            sum += term;
+        }
+        return (-2.0 / PI.sqrt() * (-x * x).exp() * sum).ln_1p();
+    }
+    let mut fraction = x;
+    for k in (1..=100).rev() {
+        fraction = x + (k as f64 / 2.0) / fraction;
+    }
+    -x * x - 0.5 * PI.ln() - fraction.ln()
+}
+
+/// ln Γ(x) for x > 0 (Lanczos, g = 7).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    use std::f64::consts::PI;
+    if x < 0.5 {
+        // reflection: Γ(x)·Γ(1 − x) = π / sin(πx)
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |acc, (i, c)| acc + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// Minimizer of `f` by Nelder–Mead from `start` (initial simplex steps of
+/// 0.5), restarted once from the first optimum to escape a collapsed simplex.
+fn nelder_mead(f: impl Fn(&[f64]) -> f64, start: &[f64]) -> Vec<f64> {
+    let dim = start.len();
+    let mut best = start.to_vec();
+    for _ in 0..2 {
+        let mut simplex: Vec<Vec<f64>> = vec![best.clone()];
+        for i in 0..dim {
+            let mut p = best.clone();
+            p[i] += 0.5;
+            simplex.push(p);
+        }
+        let mut values: Vec<f64> = simplex.iter().map(|p| f(p)).collect();
+        for _ in 0..5000 {
+            let mut order: Vec<usize> = (0..=dim).collect();
+            order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+            simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+            values = order.iter().map(|&i| values[i]).collect();
+            if values[dim] - values[0] <= 1e-12 * (1.0 + values[0].abs()) {
+                break;
+            }
+            let centroid: Vec<f64> = (0..dim)
+                .map(|j| simplex[..dim].iter().map(|p| p[j]).sum::<f64>() / dim as f64)
+                .collect();
+            // centroid + t·(worst − centroid)
+            let along = |t: f64| -> Vec<f64> {
+                centroid.iter().zip(&simplex[dim]).map(|(c, w)| c + t * (w - c)).collect()
+            };
+            let reflected = along(-1.0);
+            let fr = f(&reflected);
+            if fr < values[0] {
+                let expanded = along(-2.0);
+                let fe = f(&expanded);
+                (simplex[dim], values[dim]) =
+                    if fe < fr { (expanded, fe) } else { (reflected, fr) };
+            } else if fr < values[dim - 1] {
+                (simplex[dim], values[dim]) = (reflected, fr);
+            } else {
+                let contracted = along(if fr < values[dim] { -0.5 } else { 0.5 });
+                let fc = f(&contracted);
+                if fc < fr.min(values[dim]) {
+                    (simplex[dim], values[dim]) = (contracted, fc);
+                } else {
+                    for i in 1..=dim {
+                        simplex[i] = simplex[0]
+                            .iter()
+                            .zip(&simplex[i])
+                            .map(|(b, p)| b + 0.5 * (p - b))
+                            .collect();
+                        values[i] = f(&simplex[i]);
+                    }
+                }
+            }
+        }
+        let i = (0..=dim).min_by(|&a, &b| values[a].total_cmp(&values[b])).unwrap_or(0);
+        best = simplex.swap_remove(i);
+    }
+    best
+}
+
+/// Hurwitz zeta ζ(s, q) = Σ_{k≥0} (k + q)^(−s) for s > 1, q > 0.
+///
+/// # Logic
+/// Sums the first 10 terms directly and the rest by Euler–Maclaurin:
+/// ∫ + ½·f(a) + Σⱼ B₂ⱼ/(2j)!·s(s+1)…(s+2j−2)·a^(−s−2j+1) with a = q + 10,
+/// which is accurate to double precision for moderate s.
+fn hurwitz_zeta(s: f64, q: f64) -> f64 {
+    const DIRECT: usize = 10;
+    // B₂ⱼ / (2j)! for j = 1..=7
+    const BERNOULLI: [f64; 7] = [
+        1.0 / 12.0,
+        -1.0 / 720.0,
+        1.0 / 30240.0,
+        -1.0 / 1209600.0,
+        1.0 / 47900160.0,
+        -691.0 / 1307674368000.0,
+        1.0 / 74724249600.0,
+    ];
+    let mut sum: f64 = (0..DIRECT).map(|k| (k as f64 + q).powf(-s)).sum();
+    let a = q + DIRECT as f64;
+    sum += a.powf(1.0 - s) / (s - 1.0) + 0.5 * a.powf(-s);
+    // rising factorial s(s+1)…(s+2j−2) times a^(−s−2j+1)
+    let mut term = s * a.powf(-s - 1.0);
+    for (j, b) in BERNOULLI.iter().enumerate() {
+        sum += b * term;
+        let m = 2.0 * j as f64 + s;
+        term *= (m + 1.0) * (m + 2.0) / (a * a);
+    }
+    sum
+}
+
+/// Maximizer of a unimodal `f` on [lo, hi], to within `tol`.
+fn golden_section_max(f: impl Fn(f64) -> f64, mut lo: f64, mut hi: f64, tol: f64) -> f64 {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut x1 = hi - ratio * (hi - lo);
+    let mut x2 = lo + ratio * (hi - lo);
+    let (mut f1, mut f2) = (f(x1), f(x2));
+    while hi - lo > tol {
+        if f1 < f2 {
+            lo = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = lo + ratio * (hi - lo);
+            f2 = f(x2);
+        } else {
+            hi = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = hi - ratio * (hi - lo);
+            f1 = f(x1);
+        }
+    }
+    (lo + hi) / 2.0
 }
 
 #[cfg(test)]
 mod tests {
-    use super::mle_power_law_exponent;
+    use super::{erfc, hurwitz_zeta, ln_erfc, ln_gamma, ln_poisson_tail, mle_power_law_exponent};
     use std::collections::HashMap;
 
     #[test]
-    /// Synthetic degree counts ∝ k^(−3), k=1..10 → expect estimated exponent ≈ 3 within 1%.
+    /// Synthetic degree counts ∝ k^(−3), k=1..100 → expect estimated exponent ≈ 3 within 1%.
     fn mle_power_law_exponent_exact() {
         let alpha_true = 3.0;
         let k_min = 1;
         let mut counts = HashMap::new();
-        // generate counts = round(1000 * k^(-3))
-        for k in 1..=10 {
+        // generate counts = round(10⁶ * k^(-3)); coarser or shorter tables bias the MLE
+        for k in 1..=100 {
             let weight = (k as f64).powf(-alpha_true);
-            counts.insert(k, (weight * 1000.0).round() as usize);
+            counts.insert(k, (weight * 1e6).round() as usize);
         }
 
         let alpha_hat = mle_power_law_exponent(&counts, k_min);
@@ -91,4 +1119,48 @@ mod tests {
             rel_err
         );
     }
+
+    #[test]
+    /// ζ(2) = π²/6, ζ(4) = π⁴/90, ζ(2, ½) = π²/2, and ζ(s, q) = ζ(s, q+1) + q^(−s).
+    fn hurwitz_zeta_known_values() {
+        use std::f64::consts::PI;
+        assert!((hurwitz_zeta(2.0, 1.0) - PI * PI / 6.0).abs() < 1e-14);
+        assert!((hurwitz_zeta(4.0, 1.0) - PI.powi(4) / 90.0).abs() < 1e-14);
+        assert!((hurwitz_zeta(2.0, 0.5) - PI * PI / 2.0).abs() < 1e-13);
+        let (s, q) = (1.3, 7.0);
+        let shifted = hurwitz_zeta(s, q + 1.0) + q.powf(-s);
+        assert!((hurwitz_zeta(s, q) - shifted).abs() < 1e-12 * shifted);
+    }
+
+    #[test]
+    /// erfc and ln Γ against reference values, across the series /
+    /// continued-fraction switch and deep into the tail.
+    fn special_functions() {
+        let erfc_reference = [
+            (0.1, 0.8875370839817152),
+            (1.0, 0.15729920705028513),
+            (2.49, 0.000429287867733913),
+            (2.5, 0.0004069520174449589),
+            (3.0, 2.2090496998585438e-05),
+            (6.0, 2.1519736712498916e-17),
+            (10.0, 2.088487583762545e-45),
+        ];
+        for (x, expected) in erfc_reference {
+            assert!((erfc(x) / expected - 1.0).abs() < 1e-13, "erfc({})", x);
+        }
+        assert!((erfc(-1.0) - (2.0 - 0.15729920705028513)).abs() < 1e-15);
+        assert!((ln_erfc(-1.0) - (2.0 - 0.15729920705028513f64).ln()).abs() < 1e-15);
+        // erfc(40) underflows, its logarithm does not
+        assert!((ln_erfc(40.0) + 1600.0 + 40f64.ln() + 0.5 * std::f64::consts::PI.ln()).abs() < 1e-3);
+
+        assert!((ln_gamma(0.5) - 0.5723649429247004).abs() < 1e-14);
+        assert!((ln_gamma(10.0) - 12.801827480081467).abs() < 1e-13);
+        assert!((ln_gamma(1000.5) / 5908.674175848678 - 1.0).abs() < 1e-14);
+        assert!((ln_gamma(0.1) - 2.2527126517342055).abs() < 1e-13);
+
+        // P(K ≥ 1) = 1 − e^(−μ), and a far tail matches its leading term
+        assert!((ln_poisson_tail(2.0, 1) - (-(-2f64).exp()).ln_1p()).abs() < 1e-14);
+        let leading = -0.5 + 30.0 * 0.5f64.ln() - ln_gamma(31.0);
+        assert!((ln_poisson_tail(0.5, 30) - leading).abs() < 0.02);
+    }
 }
@@ -1,32 +1,49 @@
 //! Module `utils`: timing and logging helpers.
 
-use std::time::Instant;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
 
-/// Measure execution time of closure `f`, print elapsed with `label`,
-/// and return `f`’s result.
+/// Measure execution time of closure `f`, log elapsed with `label` to
+/// stderr, and return `f`’s result.
 ///
 /// # Inputs
 /// - `label`: description of timed block
 /// - `f`: zero-arg closure
 ///
 /// # Outputs
-/// - returns whatever `f()` returns
-pub fn measure_time<F, R>(label: &str, f: F) -> R
+/// - whatever `f()` returns, with the elapsed seconds (as recorded in the
+///   `--out-dir` manifest)
+pub fn measure_time<F, R>(label: &str, f: F) -> (R, f64)
+where
+    F: FnOnce() -> R,
+{
+    let (result, elapsed) = time_it(f);
+    log_elapsed(label, elapsed);
+    (result, elapsed.as_secs_f64())
+}
+
+/// Log `elapsed` under `label` to stderr, keeping timings out of reports
+/// written to stdout or a file.
+pub fn log_elapsed(label: &str, elapsed: Duration) {
+    eprintln!("[{}] completed in {}.{:03} secs",
+              label, elapsed.as_secs(), elapsed.subsec_millis());
+}
+
+/// Run closure `f` and return its result together with the elapsed time.
+pub fn time_it<F, R>(f: F) -> (R, Duration)
 where
     F: FnOnce() -> R,
 {
     let start = Instant::now();
     let result = f();
-    let elapsed = start.elapsed();
-    println!("[{}] completed in {}.{:03} secs",
-             label, elapsed.as_secs(), elapsed.subsec_millis());
-    result
+    (result, start.elapsed())
 }
 
-/// Print a formatted section header.
+/// Write a formatted section header to `out`.
 ///
 /// # Inputs
+/// - `out`: destination writer (stdout, report file, …)
 /// - `title`: text to display
-pub fn print_section(title: &str) {
-    println!("\n=== {} ===", title);
+pub fn write_section<W: Write>(out: &mut W, title: &str) -> io::Result<()> {
+    writeln!(out, "\n=== {} ===", title)
 }
@@ -0,0 +1,46 @@
+//! Graph fixtures shared by the integration tests.
+#![allow(dead_code)] // each test crate uses its own subset
+
+use petgraph::{Graph, Undirected};
+use std::collections::HashSet;
+
+/// K4 on payloads 0..4 with a pendant path 3–4–5–6 hanging off node 3.
+pub fn k4_with_tail() -> Graph<usize, (), Undirected> {
+    let mut g = Graph::new_undirected();
+    let n: Vec<_> = (0..7).map(|i| g.add_node(i)).collect();
+    for i in 0..4 {
+        for j in i + 1..4 {
+            g.add_edge(n[i], n[j], ());
+        }
+    }
+    g.add_edge(n[3], n[4], ());
+    g.add_edge(n[4], n[5], ());
+    g.add_edge(n[5], n[6], ());
+    g
+}
+
+/// Endless deterministic stream of node-index pairs in `0..n` (LCG seeded
+/// with `state`); pairs may repeat and may be self-loops.
+pub fn lcg_pairs(n: usize, mut state: u64) -> impl Iterator<Item = (usize, usize)> {
+    std::iter::repeat_with(move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((state >> 33) as usize % n, (state >> 17) as usize % n)
+    })
+}
+
+/// Small deterministic pseudo-random simple graph on payloads 0..n with
+/// `edges` distinct edges drawn from [`lcg_pairs`].
+pub fn lcg_graph(n: usize, edges: usize, state: u64) -> Graph<usize, (), Undirected> {
+    let mut g = Graph::new_undirected();
+    let nodes: Vec<_> = (0..n).map(|i| g.add_node(i)).collect();
+    let mut seen = HashSet::new();
+    for (u, v) in lcg_pairs(n, state) {
+        if seen.len() == edges {
+            break;
+        }
+        if u != v && seen.insert((u.min(v), u.max(v))) {
+            g.add_edge(nodes[u], nodes[v], ());
+        }
+    }
+    g
+}
@@ -1,8 +1,17 @@
 //! Tests for closeness and betweenness centrality implementations in `graph_analysis`.
 
+mod common;
+
 use petgraph::Graph;
 use petgraph::Undirected;
-use ds210_project::graph_analysis::{closeness_centrality, betweenness_centrality};
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, VecDeque};
+use ds210_project::graph_analysis::{
+    BetweennessOptions, BetweennessSampling, CentralityError, ClosenessVariant, KatzOptions,
+    PageRankOptions, PowerIterationOptions, betweenness_centrality, betweenness_centrality_with,
+    betweenness_sampled, closeness_centrality, closeness_centrality_with, eigenvector_centrality,
+    katz_centrality, pagerank, personalized_pagerank,
+};
 
 /// Build a 3-node triangle: every pair connected.
 /// All shortest paths are direct, so betweenness should be zero.
@@ -42,6 +51,35 @@ fn test_closeness_triangle() {
     }
 }
 
+#[test]
+/// Path 0–1–2–3 plus a separate edge 4–5: the plain variant inflates the
+/// small component, Wasserman–Faust and harmonic do not.
+fn test_closeness_variants_disconnected() {
+    let mut g = path_graph_4();
+    let a = g.add_node(4);
+    let b = g.add_node(5);
+    g.add_edge(a, b, ());
+
+    let plain = closeness_centrality(&g);
+    assert!((plain[&4] - 5.0).abs() < 1e-12);
+    assert!((plain[&1] - 5.0 / 4.0).abs() < 1e-12);
+    assert!(plain[&4] > plain[&1]);
+
+    let wf = closeness_centrality_with(&g, ClosenessVariant::WassermanFaust);
+    assert!((wf[&4] - 0.2).abs() < 1e-12);
+    assert!((wf[&1] - (3.0 / 5.0) * (3.0 / 4.0)).abs() < 1e-12);
+    assert!(wf[&4] < wf[&1]);
+
+    let harmonic = closeness_centrality_with(&g, ClosenessVariant::Harmonic);
+    assert!((harmonic[&4] - 1.0).abs() < 1e-12);
+    assert!((harmonic[&1] - 2.5).abs() < 1e-12);
+    assert!((harmonic[&0] - (1.0 + 0.5 + 1.0 / 3.0)).abs() < 1e-12);
+
+    // on a connected graph Wasserman–Faust is the plain formula
+    let g = path_graph_4();
+    assert_eq!(closeness_centrality(&g), closeness_centrality_with(&g, ClosenessVariant::WassermanFaust));
+}
+
 #[test]
 /// In a triangle, no node lies on any shortest path between others → betweenness = 0.
 fn test_betweenness_triangle() {
@@ -70,3 +108,294 @@ fn test_betweenness_path4() {
     assert!((betw[&0] - 0.0).abs() < 1e-6);
     assert!((betw[&3] - 0.0).abs() < 1e-6);
 }
+
+#[test]
+/// Normalisation, halving and endpoint credit on path 0–1–2–3.
+fn test_betweenness_options_path4() {
+    let g = path_graph_4();
+    let with = |opts: BetweennessOptions| betweenness_centrality_with(&g, &opts);
+
+    let norm = with(BetweennessOptions { normalized: true, ..Default::default() });
+    assert!((norm[&1] - 4.0 / 6.0).abs() < 1e-12);
+    // normalised scores already count pairs once, so halving changes nothing
+    let both = with(BetweennessOptions { normalized: true, undirected: true, ..Default::default() });
+    assert_eq!(norm, both);
+
+    let half = with(BetweennessOptions { undirected: true, ..Default::default() });
+    assert!((half[&1] - 2.0).abs() < 1e-12);
+
+    // endpoints: each node is also an end of the 6 ordered pairs it belongs to
+    let ends = with(BetweennessOptions { endpoints: true, ..Default::default() });
+    assert!((ends[&0] - 6.0).abs() < 1e-12);
+    assert!((ends[&1] - 10.0).abs() < 1e-12);
+    let ends_norm = with(BetweennessOptions { endpoints: true, normalized: true, ..Default::default() });
+    assert!((ends_norm[&1] - 10.0 / 12.0).abs() < 1e-12);
+}
+
+#[test]
+/// Source/target subsets: a single pair credits only the nodes between them,
+/// and splitting the sources partitions the full scores.
+fn test_betweenness_subsets() {
+    let g = path_graph_4();
+    let pair = betweenness_centrality_with(
+        &g,
+        &BetweennessOptions { sources: Some(vec![0]), targets: Some(vec![3]), ..Default::default() },
+    );
+    assert_eq!((pair[&0], pair[&1], pair[&2], pair[&3]), (0.0, 1.0, 1.0, 0.0));
+
+    let g = lcg_multigraph(30, 70, 4);
+    let full = betweenness_centrality(&g);
+    let part = |ids: Vec<usize>| {
+        betweenness_centrality_with(&g, &BetweennessOptions { sources: Some(ids), ..Default::default() })
+    };
+    let a = part((100..115).collect());
+    let b = part((115..130).chain([999]).collect());
+    for (node, &x) in &full {
+        assert!((a[node] + b[node] - x).abs() <= 1e-9 * x.max(1.0), "node {}", node);
+    }
+}
+
+/// Small deterministic pseudo-random multigraph on payloads 100..100+n: every
+/// pair from [`common::lcg_pairs`] becomes an edge, so it may contain parallel
+/// edges, self-loops and several components.
+fn lcg_multigraph(n: usize, edges: usize, state: u64) -> Graph<usize, (), Undirected> {
+    let mut g = Graph::new_undirected();
+    let nodes: Vec<_> = (0..n).map(|i| g.add_node(100 + i)).collect();
+    for (u, v) in common::lcg_pairs(n, state).take(edges) {
+        g.add_edge(nodes[u], nodes[v], ());
+    }
+    g
+}
+
+/// The original single-threaded HashMap-based Brandes, kept as a reference.
+fn reference_betweenness(graph: &Graph<usize, (), Undirected>) -> HashMap<usize, f64> {
+    let mut cb: HashMap<NodeIndex, f64> = graph.node_indices().map(|u| (u, 0.0)).collect();
+    for s in graph.node_indices() {
+        let mut stack = Vec::new();
+        let mut pred: HashMap<NodeIndex, Vec<NodeIndex>> =
+            graph.node_indices().map(|v| (v, Vec::new())).collect();
+        let mut sigma: HashMap<NodeIndex, f64> = graph.node_indices().map(|v| (v, 0.0)).collect();
+        let mut dist: HashMap<NodeIndex, i32> = graph.node_indices().map(|v| (v, -1)).collect();
+        let mut queue = VecDeque::new();
+        sigma.insert(s, 1.0);
+        dist.insert(s, 0);
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            stack.push(v);
+            let dv = dist[&v];
+            for w in graph.neighbors(v) {
+                if dist[&w] < 0 {
+                    dist.insert(w, dv + 1);
+                    queue.push_back(w);
+                }
+                if dist[&w] == dv + 1 {
+                    *sigma.get_mut(&w).unwrap() += sigma[&v];
+                    pred.get_mut(&w).unwrap().push(v);
+                }
+            }
+        }
+        let mut delta: HashMap<NodeIndex, f64> = graph.node_indices().map(|v| (v, 0.0)).collect();
+        while let Some(w) = stack.pop() {
+            for &v in &pred[&w] {
+                let c = (sigma[&v] / sigma[&w]) * (1.0 + delta[&w]);
+                *delta.get_mut(&v).unwrap() += c;
+            }
+            if w != s {
+                *cb.get_mut(&w).unwrap() += delta[&w];
+            }
+        }
+    }
+    cb.into_iter().map(|(idx, val)| (graph[idx], val)).collect()
+}
+
+#[test]
+/// The parallel dense-array Brandes matches the HashMap reference, including
+/// on multigraphs with self-loops and several components.
+fn test_betweenness_matches_reference() {
+    for seed in 0..6 {
+        let g = lcg_multigraph(40, 90, seed);
+        let fast = betweenness_centrality(&g);
+        let slow = reference_betweenness(&g);
+        assert_eq!(fast.len(), slow.len());
+        for (node, &b) in &slow {
+            let f = fast[node];
+            assert!((f - b).abs() <= 1e-9 * b.max(1.0), "seed {} node {}: {} vs {}", seed, node, f, b);
+        }
+    }
+}
+
+#[test]
+/// Pivot sampling is seeded, and with as many pivots as nodes it is exact.
+fn test_betweenness_pivots() {
+    let g = lcg_multigraph(60, 150, 7);
+    let exact = betweenness_centrality(&g);
+    let all = betweenness_sampled(&g, BetweennessSampling::Pivots { pivots: 1000, seed: 1 }).unwrap();
+    assert_eq!(all.samples, 60);
+    for (node, &b) in &exact {
+        assert!((all.scores[node] - b).abs() <= 1e-9 * b.max(1.0));
+    }
+
+    let a = betweenness_sampled(&g, BetweennessSampling::Pivots { pivots: 20, seed: 5 }).unwrap();
+    let b = betweenness_sampled(&g, BetweennessSampling::Pivots { pivots: 20, seed: 5 }).unwrap();
+    assert_eq!(a.samples, 20);
+    for (node, &x) in &a.scores {
+        assert!((x - b.scores[node]).abs() <= 1e-9 * x.max(1.0));
+    }
+}
+
+#[test]
+/// Path sampling is reproducible, uses the Riondato–Kornaropoulos sample
+/// size, and stays within ε of the exact normalised scores.
+fn test_betweenness_path_sampling() {
+    let g = lcg_multigraph(80, 200, 3);
+    let n = g.node_count() as f64;
+    let sampling = BetweennessSampling::PathSampling { epsilon: 0.02, delta: 0.1, seed: 11 };
+    let a = betweenness_sampled(&g, sampling).unwrap();
+    let b = betweenness_sampled(&g, sampling).unwrap();
+    assert_eq!(a.scores, b.scores);
+
+    let vd = a.vertex_diameter_bound.unwrap();
+    let expected = (0.5 / 0.0004 * (((vd - 2) as f64).log2().floor() + 1.0 + 10f64.ln())).ceil();
+    assert_eq!(a.samples, expected as usize);
+
+    let exact = betweenness_centrality(&g);
+    for (node, &x) in &exact {
+        let err = (a.scores[node] - x).abs() / (n * (n - 1.0));
+        assert!(err <= 0.02, "node {}: error {}", node, err);
+    }
+}
+
+#[test]
+/// ε and δ outside (0, 1), including NaN, are rejected instead of sizing
+/// an unbounded sample.
+fn test_betweenness_path_sampling_rejects_bad_parameters() {
+    let g = triangle_graph();
+    for (epsilon, delta) in [(0.0, 0.1), (f64::NAN, 0.1), (1.0, 0.1), (0.1, 0.0), (0.1, 1.0), (0.1, -0.5)] {
+        let sampling = BetweennessSampling::PathSampling { epsilon, delta, seed: 1 };
+        assert!(matches!(
+            betweenness_sampled(&g, sampling),
+            Err(CentralityError::InvalidSampling { .. })
+        ));
+    }
+}
+
+#[test]
+/// PageRank sums to 1, is uniform on a cycle, and favours the hub of a star;
+/// isolated nodes keep the total mass at 1.
+fn test_pagerank_basic() {
+    let opts = PageRankOptions::default();
+
+    let mut cycle = Graph::<usize, (), Undirected>::new_undirected();
+    let n: Vec<_> = (0..5).map(|i| cycle.add_node(i)).collect();
+    for i in 0..5 {
+        cycle.add_edge(n[i], n[(i + 1) % 5], ());
+    }
+    let pr = pagerank(&cycle, &opts);
+    assert!(pr.convergence.converged);
+    for &x in pr.scores.values() {
+        assert!((x - 0.2).abs() < 1e-9);
+    }
+
+    let mut star = Graph::<usize, (), Undirected>::new_undirected();
+    let hub = star.add_node(0);
+    for i in 1..6 {
+        let leaf = star.add_node(i);
+        star.add_edge(hub, leaf, ());
+    }
+    star.add_node(99); // isolated
+    let pr = pagerank(&star, &opts);
+    let total: f64 = pr.scores.values().sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    assert!(pr.scores[&0] > pr.scores[&1]);
+    assert!(pr.scores[&1] > pr.scores[&99]);
+    assert!((pr.scores[&1] - pr.scores[&5]).abs() < 1e-12);
+
+    let capped = pagerank(&star, &PageRankOptions { max_iterations: 1, ..opts });
+    assert_eq!(capped.convergence.iterations, 1);
+    assert!(!capped.convergence.converged);
+}
+
+#[test]
+/// Personalized PageRank concentrates on the seeds; without damping it is
+/// exactly the teleport vector, and unknown seeds give `None`.
+fn test_personalized_pagerank() {
+    let g = lcg_multigraph(30, 60, 2);
+    let opts = PageRankOptions::default();
+    let ppr = personalized_pagerank(&g, &[105, 12345], &opts).unwrap();
+    let top = ppr.scores.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+    assert_eq!(*top.0, 105);
+    assert!((ppr.scores.values().sum::<f64>() - 1.0).abs() < 1e-9);
+
+    let flat = personalized_pagerank(&g, &[105, 110], &PageRankOptions { damping: 0.0, ..opts }).unwrap();
+    assert_eq!(flat.scores[&105], 0.5);
+    assert_eq!(flat.scores[&111], 0.0);
+
+    assert!(personalized_pagerank(&g, &[12345], &opts).is_none());
+}
+
+/// Star with hub payload 0 and `leaves` leaves 1..=leaves.
+fn star_graph(leaves: usize) -> Graph<usize, (), Undirected> {
+    let mut g = Graph::new_undirected();
+    let hub = g.add_node(0);
+    for i in 1..=leaves {
+        let leaf = g.add_node(i);
+        g.add_edge(hub, leaf, ());
+    }
+    g
+}
+
+#[test]
+/// On a star K1,k (bipartite, so unshifted power iteration would oscillate)
+/// λ_max = √k and the hub scores √k times a leaf; disconnected and empty
+/// graphs are rejected.
+fn test_eigenvector_centrality() {
+    let opts = PowerIterationOptions::default();
+    let ev = eigenvector_centrality(&star_graph(5), &opts).unwrap();
+    assert!(ev.convergence.converged);
+    assert!((ev.eigenvalue - 5f64.sqrt()).abs() < 1e-6);
+    assert!((ev.scores[&0] / ev.scores[&3] - 5f64.sqrt()).abs() < 1e-6);
+    let norm: f64 = ev.scores.values().map(|x| x * x).sum();
+    assert!((norm - 1.0).abs() < 1e-9);
+
+    let mut split = star_graph(3);
+    split.add_node(42);
+    assert_eq!(
+        eigenvector_centrality(&split, &opts).unwrap_err(),
+        CentralityError::Disconnected { components: 2 }
+    );
+    let empty = Graph::<usize, (), Undirected>::new_undirected();
+    assert_eq!(eigenvector_centrality(&empty, &opts).unwrap_err(), CentralityError::EmptyGraph);
+}
+
+#[test]
+/// Katz on a star matches the closed-form fixed point, works on disconnected
+/// graphs, and rejects α ≥ 1/λ_max.
+fn test_katz_centrality() {
+    let k = 4.0;
+    let alpha = 0.3; // 1/λ_max = 0.5
+    let katz = katz_centrality(
+        &star_graph(4),
+        &KatzOptions { alpha: Some(alpha), ..KatzOptions::default() },
+    )
+    .unwrap();
+    assert!(katz.convergence.converged);
+    assert!((katz.spectral_radius - 2.0).abs() < 1e-6);
+    // hub = 1 + αk·leaf, leaf = 1 + α·hub
+    let leaf = (1.0 + alpha) / (1.0 - alpha * alpha * k);
+    let hub = 1.0 + alpha * k * leaf;
+    assert!((katz.scores[&0] / katz.scores[&1] - hub / leaf).abs() < 1e-8);
+
+    let mut split = star_graph(4);
+    split.add_node(42);
+    let katz = katz_centrality(&split, &KatzOptions::default()).unwrap();
+    assert!((katz.alpha - 0.45).abs() < 1e-6);
+    assert!(katz.scores[&42] > 0.0 && katz.scores[&42] < katz.scores[&1]);
+
+    match katz_centrality(&split, &KatzOptions { alpha: Some(0.5), ..KatzOptions::default() }) {
+        Err(CentralityError::AttenuationTooLarge { alpha, spectral_radius }) => {
+            assert_eq!(alpha, 0.5);
+            assert!((spectral_radius - 2.0).abs() < 1e-6);
+        }
+        other => panic!("expected AttenuationTooLarge, got {:?}", other),
+    }
+}
@@ -0,0 +1,59 @@
+//! Tests for triangle counting and clustering coefficients in `graph_analysis`.
+
+mod common;
+
+use common::{k4_with_tail, lcg_graph};
+use ds210_project::graph_analysis::{clustering_stats, triangle_counts};
+use petgraph::prelude::NodeIndex;
+
+#[test]
+/// Hand-computed counts and coefficients on K4 plus a tail.
+fn k4_with_tail_clustering() {
+    let mut g = k4_with_tail();
+    // a parallel edge and a self-loop must not create triangles
+    let (a, b) = (g.node_indices().next().unwrap(), g.node_indices().nth(1).unwrap());
+    g.add_edge(a, b, ());
+    g.add_edge(a, a, ());
+
+    let stats = clustering_stats(&g);
+    assert_eq!(stats.triangles.total, 4);
+    assert_eq!(stats.triangles.per_node[&0], 3);
+    assert_eq!(stats.triangles.per_node[&4], 0);
+    assert_eq!(stats.local_clustering[&0], 1.0);
+    assert_eq!(stats.local_clustering[&3], 0.5);
+    assert_eq!(stats.local_clustering[&6], 0.0);
+    assert!((stats.average_clustering - 3.5 / 7.0).abs() < 1e-12);
+    // 3·4 closed out of 3·3 + 6 + 1 + 1 connected triples
+    assert!((stats.transitivity - 12.0 / 17.0).abs() < 1e-12);
+    assert_eq!(stats.clustering_by_degree[&3], 1.0);
+    assert_eq!(stats.clustering_by_degree[&2], 0.0);
+    assert_eq!(stats.clustering_by_degree.get(&1), None);
+}
+
+#[test]
+/// Degree-ordered counting matches an O(n³) scan on random graphs.
+fn triangles_match_brute_force() {
+    for seed in 0..6 {
+        let g = lcg_graph(20, 70, seed);
+        let adj = |u: usize, v: usize| g.find_edge(NodeIndex::new(u), NodeIndex::new(v)).is_some();
+        let mut per_node = [0; 20];
+        let mut total = 0;
+        for u in 0..20 {
+            for v in u + 1..20 {
+                for w in v + 1..20 {
+                    if adj(u, v) && adj(v, w) && adj(u, w) {
+                        total += 1;
+                        per_node[u] += 1;
+                        per_node[v] += 1;
+                        per_node[w] += 1;
+                    }
+                }
+            }
+        }
+        let counts = triangle_counts(&g);
+        assert_eq!(counts.total, total, "seed {}", seed);
+        for (v, &t) in per_node.iter().enumerate() {
+            assert_eq!(counts.per_node[&v], t, "seed {} node {}", seed, v);
+        }
+    }
+}
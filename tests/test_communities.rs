@@ -0,0 +1,102 @@
+//! Tests for edge betweenness, modularity and Girvan–Newman in `graph_analysis`.
+
+use ds210_project::graph_analysis::{
+    PathSampling, edge_betweenness_centrality, girvan_newman, modularity, path_length_stats,
+};
+use petgraph::{Graph, Undirected};
+use std::collections::HashMap;
+
+/// Two triangles {0,1,2} and {3,4,5} joined by the bridge 2–3.
+fn barbell() -> Graph<usize, (), Undirected> {
+    let mut g = Graph::new_undirected();
+    let n: Vec<_> = (0..6).map(|i| g.add_node(i)).collect();
+    for &(a, b) in &[(0, 1), (1, 2), (0, 2), (3, 4), (4, 5), (3, 5), (2, 3)] {
+        g.add_edge(n[a], n[b], ());
+    }
+    g
+}
+
+/// `k` cliques of size `size` joined in a ring by single edges.
+fn ring_of_cliques(k: usize, size: usize) -> Graph<usize, (), Undirected> {
+    let mut g = Graph::new_undirected();
+    let n: Vec<_> = (0..k * size).map(|i| g.add_node(i)).collect();
+    for c in 0..k {
+        for i in 0..size {
+            for j in i + 1..size {
+                g.add_edge(n[c * size + i], n[c * size + j], ());
+            }
+        }
+        g.add_edge(n[c * size], n[((c + 1) % k) * size + 1], ());
+    }
+    g
+}
+
+#[test]
+/// The bridge carries all 3·3 cross pairs in both directions; edges inside
+/// a triangle away from the bridge carry only their own pair.
+fn edge_betweenness_barbell() {
+    let eb = edge_betweenness_centrality(&barbell());
+    assert_eq!(eb.len(), 7);
+    assert!((eb[&(2, 3)] - 18.0).abs() < 1e-12);
+    assert!((eb[&(0, 1)] - 2.0).abs() < 1e-12);
+    assert!((eb[&(1, 2)] - 8.0).abs() < 1e-12);
+}
+
+#[test]
+/// Summed over edges, edge betweenness is the total length of all shortest paths.
+fn edge_betweenness_sums_to_path_lengths() {
+    let g = ring_of_cliques(5, 4);
+    let eb = edge_betweenness_centrality(&g);
+    let ps = path_length_stats(&g, PathSampling::Exact);
+    let total: f64 = eb.values().sum();
+    let expected = ps.average_path_length * ps.reachable_pairs as f64;
+    assert!((total - expected).abs() < 1e-9 * expected, "{} vs {}", total, expected);
+}
+
+#[test]
+/// Modularity of the two triangles: 2·(3/7 − (7/14)²).
+fn modularity_barbell() {
+    let g = barbell();
+    let halves: HashMap<usize, usize> = (0..6).map(|v| (v, v / 3)).collect();
+    assert!((modularity(&g, &halves) - (6.0 / 7.0 - 0.5)).abs() < 1e-12);
+    let one: HashMap<usize, usize> = (0..6).map(|v| (v, 0)).collect();
+    assert!(modularity(&g, &one).abs() < 1e-12);
+}
+
+#[test]
+/// Girvan–Newman cuts the bridge first and the best cut is the two triangles.
+fn girvan_newman_barbell() {
+    let gn = girvan_newman(&barbell(), None);
+    assert_eq!(gn.splits[0].edges_removed, 1);
+    assert_eq!(gn.splits[0].communities, 2);
+    assert_eq!(gn.best_level, 1);
+    assert!((gn.best_modularity - (6.0 / 7.0 - 0.5)).abs() < 1e-12);
+    let p = &gn.best_partition;
+    assert_eq!(p[&0], p[&1]);
+    assert_eq!(p[&1], p[&2]);
+    assert_ne!(p[&2], p[&3]);
+    assert_eq!(p[&3], p[&5]);
+    // removing every edge ends in singletons
+    assert_eq!(gn.splits.last().unwrap().communities, 6);
+    assert_eq!(gn.partition_at(0), gn.initial);
+}
+
+#[test]
+/// On a ring of cliques the best partition recovers the cliques, and
+/// `max_splits` stops the dendrogram early.
+fn girvan_newman_ring_of_cliques() {
+    let g = ring_of_cliques(4, 5);
+    let gn = girvan_newman(&g, None);
+    let p = &gn.best_partition;
+    for c in 0..4 {
+        for i in 1..5 {
+            assert_eq!(p[&(c * 5)], p[&(c * 5 + i)], "clique {}", c);
+        }
+        assert_ne!(p[&(c * 5)], p[&(((c + 1) % 4) * 5)]);
+    }
+    assert_eq!(gn.splits[gn.best_level - 1].communities, 4);
+
+    let short = girvan_newman(&g, Some(2));
+    assert_eq!(short.splits.len(), 2);
+    assert_eq!(short.splits[1].modularity, gn.splits[1].modularity);
+}
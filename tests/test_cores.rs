@@ -0,0 +1,84 @@
+//! Tests for the k-core decomposition in `graph_analysis`.
+
+mod common;
+
+use common::{k4_with_tail, lcg_graph};
+use ds210_project::graph_analysis::core_numbers;
+use petgraph::{Graph, Undirected};
+use std::collections::HashMap;
+
+/// The shared K4-with-tail fixture plus an isolated node 7.
+fn k4_with_tail_and_isolated() -> Graph<usize, (), Undirected> {
+    let mut g = k4_with_tail();
+    g.add_node(7);
+    g
+}
+
+/// Core numbers from the definition: for each k, delete nodes of degree < k
+/// until none remain; survivors have core number ≥ k.
+fn naive_core_numbers(g: &Graph<usize, (), Undirected>) -> HashMap<usize, usize> {
+    let mut core: HashMap<usize, usize> = g.node_weights().map(|&v| (v, 0)).collect();
+    for k in 1..g.node_count() {
+        let mut alive = vec![true; g.node_count()];
+        loop {
+            let drop: Vec<_> = g
+                .node_indices()
+                .filter(|&u| alive[u.index()])
+                .filter(|&u| g.neighbors(u).filter(|w| alive[w.index()]).count() < k)
+                .collect();
+            if drop.is_empty() {
+                break;
+            }
+            for u in drop {
+                alive[u.index()] = false;
+            }
+        }
+        for u in g.node_indices().filter(|u| alive[u.index()]) {
+            core.insert(g[u], k);
+        }
+    }
+    core
+}
+
+#[test]
+/// K4 is the 3-core, the tail is 1-core and the isolated node is 0-core.
+fn k4_with_tail_cores() {
+    let g = k4_with_tail_and_isolated();
+    let cores = core_numbers(&g);
+    assert_eq!(cores.degeneracy, 3);
+    for v in 0..4 {
+        assert_eq!(cores.core_numbers[&v], 3);
+    }
+    for v in 4..7 {
+        assert_eq!(cores.core_numbers[&v], 1);
+    }
+    assert_eq!(cores.core_numbers[&7], 0);
+    assert_eq!(cores.order.len(), 8);
+
+    assert_eq!(cores.k_core_nodes(3), vec![0, 1, 2, 3]);
+    let k3 = cores.k_core(&g, 3);
+    assert_eq!((k3.node_count(), k3.edge_count()), (4, 6));
+    assert_eq!(cores.k_core(&g, 4).node_count(), 0);
+
+    assert_eq!(cores.core_sizes(), vec![8, 7, 4, 4]);
+    let dist = cores.core_size_distribution();
+    assert_eq!(dist[&3], 4);
+    assert_eq!(dist[&1], 3);
+    assert_eq!(dist.get(&2), None);
+}
+
+#[test]
+/// The bucket-queue decomposition agrees with the definition on random graphs.
+fn core_numbers_match_definition() {
+    for seed in 0..8 {
+        let g = lcg_graph(14, 35, seed);
+        let cores = core_numbers(&g);
+        assert_eq!(cores.core_numbers, naive_core_numbers(&g), "seed {}", seed);
+        assert_eq!(cores.degeneracy, *cores.core_numbers.values().max().unwrap());
+
+        // every node of the k-core has at least k neighbours inside it
+        let k = cores.degeneracy;
+        let kc = cores.k_core(&g, k);
+        assert!(kc.node_indices().all(|u| kc.neighbors(u).count() >= k), "seed {}", seed);
+    }
+}
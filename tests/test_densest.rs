@@ -0,0 +1,112 @@
+//! Tests for the densest-subgraph routines in `graph_analysis`.
+
+mod common;
+
+use common::{k4_with_tail, lcg_graph};
+use ds210_project::graph_analysis::{
+    densest_subgraph_exact, densest_subgraph_greedy_plus_plus, densest_subgraph_peel,
+    densest_subgraph_peel_trace,
+};
+use petgraph::{Graph, Undirected};
+
+/// Exact max density by enumerating every non-empty node subset.
+fn brute_force_density(g: &Graph<usize, (), Undirected>) -> f64 {
+    let n = g.node_count();
+    let mut best: f64 = 0.0;
+    for mask in 1u32..(1 << n) {
+        let edges = g
+            .edge_indices()
+            .filter(|&e| {
+                let (a, b) = g.edge_endpoints(e).unwrap();
+                mask & (1 << a.index()) != 0 && mask & (1 << b.index()) != 0
+            })
+            .count();
+        best = best.max(edges as f64 / mask.count_ones() as f64);
+    }
+    best
+}
+
+#[test]
+/// The K4 core (density 6/4) is found and the tail is peeled first.
+fn peel_finds_k4_core() {
+    let ds = densest_subgraph_peel(&k4_with_tail());
+    let mut nodes = ds.nodes.clone();
+    nodes.sort();
+    assert_eq!(nodes, vec![0, 1, 2, 3]);
+    assert!((ds.density - 1.5).abs() < 1e-12);
+}
+
+#[test]
+/// The trace covers every node; the curve starts at |E|/|V| and its maximum is the result.
+fn peel_trace_curve() {
+    let g = k4_with_tail();
+    let peel = densest_subgraph_peel_trace(&g);
+    assert_eq!(peel.order.len(), 7);
+    assert_eq!(peel.density_curve.len(), 7);
+    assert!((peel.density_curve[0] - 9.0 / 7.0).abs() < 1e-12);
+    assert_eq!(peel.order[0], 6, "the degree-1 tail end goes first");
+    let max = peel.density_curve.iter().cloned().fold(0.0, f64::max);
+    assert_eq!(max, peel.subgraph.density);
+    // last node alone has density 0
+    assert_eq!(*peel.density_curve.last().unwrap(), 0.0);
+}
+
+#[test]
+/// On small random graphs the peel is within a factor 2 of the brute-force optimum.
+fn peel_is_two_approximation() {
+    for seed in 0..8 {
+        let g = lcg_graph(11, 22, seed);
+        let opt = brute_force_density(&g);
+        let ds = densest_subgraph_peel(&g);
+        assert!(ds.density <= opt + 1e-12);
+        assert!(2.0 * ds.density >= opt - 1e-12, "seed {}: {} vs {}", seed, ds.density, opt);
+    }
+}
+
+#[test]
+/// The max-flow solver matches brute force and its certificate verifies.
+fn exact_matches_brute_force() {
+    for seed in 0..8 {
+        let g = lcg_graph(11, 22, seed);
+        let opt = brute_force_density(&g);
+        let exact = densest_subgraph_exact(&g);
+        assert!((exact.subgraph.density - opt).abs() < 1e-12, "seed {}", seed);
+        assert!(exact.certificate.verify(&g), "seed {}", seed);
+        assert_eq!(exact.subgraph.nodes.len(), exact.certificate.nodes);
+    }
+}
+
+#[test]
+/// A certificate claiming a lower density than the optimum cannot verify.
+fn certificate_rejects_tampering() {
+    let g = k4_with_tail();
+    let mut cert = densest_subgraph_exact(&g).certificate;
+    assert_eq!((cert.edges, cert.nodes), (6, 4));
+    assert!(cert.verify(&g));
+
+    cert.edges = 5;
+    assert!(!cert.verify(&g));
+    cert.edges = 6;
+    cert.orientation.pop();
+    assert!(!cert.verify(&g));
+}
+
+#[test]
+/// Greedy++ brackets the optimum every pass and reaches it on small graphs.
+fn greedy_plus_plus_converges() {
+    for seed in 0..8 {
+        let g = lcg_graph(11, 22, seed);
+        let opt = brute_force_density(&g);
+        let gpp = densest_subgraph_greedy_plus_plus(&g, 60);
+        assert_eq!(gpp.trace.len(), 60);
+        for step in &gpp.trace {
+            assert!(step.best_density <= opt + 1e-12);
+            assert!(step.upper_bound >= opt - 1e-12, "seed {}: {:?}", seed, step);
+        }
+        // first pass is plain peeling
+        assert_eq!(gpp.trace[0].density, densest_subgraph_peel(&g).density);
+        assert!((gpp.subgraph.density - opt).abs() < 1e-12, "seed {}", seed);
+        let last = gpp.trace.last().unwrap();
+        assert!(last.upper_bound - opt < 0.25 * opt, "seed {}: {:?}", seed, last);
+    }
+}
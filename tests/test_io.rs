@@ -0,0 +1,142 @@
+//! Tests for the edge-list loaders in `io`.
+
+use ds210_project::io::{LoadError, load_edge_list, read_edge_list};
+use flate2::{Compression, write::GzEncoder};
+use std::fs;
+use std::io::Write;
+
+const SNAP_STYLE: &str = "\
+# Undirected graph: toy.txt
+# Nodes: 3 Edges: 2
+% KONECT-style comment
+
+10\t20
+20   30  1.5  1136073600
+";
+
+#[test]
+/// Comments, blank lines, tabs, runs of spaces and trailing columns are all accepted.
+fn read_edge_list_snap_header_and_tabs() {
+    let g = read_edge_list(SNAP_STYLE.as_bytes()).unwrap();
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g.edge_count(), 2);
+    let mut ids: Vec<usize> = g.node_weights().copied().collect();
+    ids.sort();
+    assert_eq!(ids, vec![10, 20, 30]);
+}
+
+#[test]
+/// Parse errors name the 1-based line that failed and keep the raw text.
+fn read_edge_list_reports_line_number() {
+    let err = read_edge_list("# header\n1 2\n3 x\n".as_bytes()).unwrap_err();
+    assert!(
+        matches!(&err, LoadError::InvalidId { line: 3, token, text, .. } if token == "x" && text == "3 x"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+/// A one-column row is a `MissingField` error, not a panic.
+fn read_edge_list_single_column_is_error() {
+    let err = read_edge_list("1 2\n7\n".as_bytes()).unwrap_err();
+    assert!(matches!(&err, LoadError::MissingField { line: 2, text, .. } if text == "7"), "{:?}", err);
+}
+
+#[test]
+/// Missing files and corrupt gzip streams are distinguishable.
+fn load_edge_list_open_and_gzip_errors() {
+    let missing = std::env::temp_dir().join("ds210-io-does-not-exist.txt");
+    let err = load_edge_list(&missing).unwrap_err();
+    assert!(matches!(&err, LoadError::Open { path, .. } if path == &missing), "{:?}", err);
+
+    let corrupt = std::env::temp_dir().join(format!("ds210-io-{}-corrupt.gz", std::process::id()));
+    let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+    enc.write_all(SNAP_STYLE.repeat(50).as_bytes()).unwrap();
+    let mut bytes = enc.finish().unwrap();
+    bytes.truncate(bytes.len() / 2);
+    fs::write(&corrupt, bytes).unwrap();
+    let err = load_edge_list(&corrupt).unwrap_err();
+    fs::remove_file(&corrupt).unwrap();
+    assert!(matches!(err, LoadError::Gzip { .. }), "{:?}", err);
+}
+
+#[test]
+/// Invalid UTF-8 inside an intact gzip stream is a `Read` error on its line,
+/// not a corrupt-gzip error.
+fn load_edge_list_invalid_utf8_in_gzip_is_read_error() {
+    let path = std::env::temp_dir().join(format!("ds210-io-{}-utf8.gz", std::process::id()));
+    let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+    enc.write_all(b"1 2\r\n3 \xff\n").unwrap();
+    fs::write(&path, enc.finish().unwrap()).unwrap();
+    let err = load_edge_list(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(err, LoadError::Read { line: 2, .. }), "{:?}", err);
+}
+
+#[test]
+/// The same content loads identically from plain and gzip files, regardless of extension.
+fn load_edge_list_sniffs_gzip() {
+    let dir = std::env::temp_dir();
+    let plain = dir.join(format!("ds210-io-{}-plain.txt", std::process::id()));
+    let gz = dir.join(format!("ds210-io-{}-gz.txt", std::process::id()));
+
+    fs::write(&plain, SNAP_STYLE).unwrap();
+    let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+    enc.write_all(SNAP_STYLE.as_bytes()).unwrap();
+    fs::write(&gz, enc.finish().unwrap()).unwrap();
+
+    let g_plain = load_edge_list(&plain).unwrap();
+    let g_gz = load_edge_list(&gz).unwrap();
+    assert_eq!(g_plain.node_count(), g_gz.node_count());
+    assert_eq!(g_plain.edge_count(), g_gz.edge_count());
+
+    fs::remove_file(plain).unwrap();
+    fs::remove_file(gz).unwrap();
+}
+
+#[test]
+/// A two-ego tarball: ego edges are added, overlapping edges are not duplicated,
+/// and features / circles / memberships are attached per ego.
+fn load_ego_facebook_toy_archive() {
+    let files: &[(&str, &str)] = &[
+        ("facebook/0.edges", "1 2\n2 1\n"),
+        ("facebook/0.feat", "1 1 0\n2 0 1\n"),
+        ("facebook/0.egofeat", "1 1\n"),
+        ("facebook/0.featnames", "0 school;id 7\n1 work;id 3\n"),
+        ("facebook/0.circles", "circle0\t1\t2\n"),
+        ("facebook/2.edges", "0 3\n"),
+        ("facebook/2.feat", "0 1\n3 0\n"),
+        ("facebook/2.egofeat", "1\n"),
+        ("facebook/2.featnames", "0 gender;anonymized feature 77\n"),
+        ("facebook/2.circles", "circle0\t3\n"),
+    ];
+    let mut builder = tar::Builder::new(Vec::new());
+    for (name, body) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(body.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, name, body.as_bytes()).unwrap();
+    }
+    let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+    enc.write_all(&builder.into_inner().unwrap()).unwrap();
+    let path = std::env::temp_dir().join(format!("ds210-io-{}-ego.tar.gz", std::process::id()));
+    fs::write(&path, enc.finish().unwrap()).unwrap();
+
+    let data = ds210_project::io::load_ego_facebook(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    // edges: 1-2, 0-1, 0-2 (ego 0), 0-3, 2-3 (ego 2); 0-2 appears in both
+    assert_eq!(data.graph.node_count(), 4);
+    assert_eq!(data.graph.edge_count(), 5);
+
+    let egos: Vec<usize> = data.egos.iter().map(|e| e.ego).collect();
+    assert_eq!(egos, vec![0, 2]);
+    let ego0 = &data.egos[0];
+    assert_eq!(ego0.alters, vec![1, 2]);
+    assert_eq!(ego0.active_features(1), Some(vec!["school;id 7"]));
+    assert_eq!(ego0.active_features(0), Some(vec!["school;id 7", "work;id 3"]));
+    assert_eq!(ego0.circles[0].members, vec![1, 2]);
+    assert_eq!(data.memberships(2), vec![0, 2]);
+}
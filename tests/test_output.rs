@@ -0,0 +1,64 @@
+//! Tests for the JSON / CSV export layer in `output`.
+
+use ds210_project::output::{self, HistogramBin, PowerLawReport, RunWriter};
+use ds210_project::stats::PowerLawMethod;
+use std::collections::HashMap;
+use std::fs;
+
+#[test]
+/// Rankings sort by descending score, break ties by node payload, and start at rank 1.
+fn rank_scores_orders_and_breaks_ties() {
+    let scores: HashMap<usize, f64> = [(3, 0.5), (1, 0.9), (2, 0.5)].into_iter().collect();
+    let ranked = output::rank_scores(&scores);
+    let order: Vec<_> = ranked.iter().map(|r| (r.rank, r.node)).collect();
+    assert_eq!(order, vec![(1, 1), (2, 2), (3, 3)]);
+}
+
+#[test]
+/// Histogram bins come out sorted by value.
+fn histogram_bins_sorted() {
+    let hist: HashMap<usize, usize> = [(5, 1), (1, 7), (2, 3)].into_iter().collect();
+    let bins = output::histogram_bins(&hist);
+    assert_eq!(
+        bins,
+        vec![
+            HistogramBin { value: 1, count: 7 },
+            HistogramBin { value: 2, count: 3 },
+            HistogramBin { value: 5, count: 1 },
+        ]
+    );
+}
+
+#[test]
+/// A run writes one JSON per analysis, a CSV for tables, and a manifest listing both.
+fn run_writer_emits_files_and_manifest() {
+    let dir = std::env::temp_dir().join(format!("ds210-output-{}", std::process::id()));
+    let mut writer = RunWriter::create(&dir, "toy.txt", 3, 2).unwrap();
+
+    let fit = PowerLawReport {
+        method: PowerLawMethod::Discrete,
+        k_min: 1,
+        k_min_selected: false,
+        tail_size: 3,
+        alpha: 2.5,
+        std_error: 0.1,
+        ks_distance: 0.2,
+    };
+    writer.record_json("power_law", &fit, 0.1).unwrap();
+    let hist: HashMap<usize, usize> = [(1, 2), (2, 1)].into_iter().collect();
+    let bins = output::histogram_bins(&hist);
+    writer.record_table("degree_distribution", &bins, &bins, 0.2).unwrap();
+    let manifest_path = writer.finish().unwrap();
+
+    let csv = fs::read_to_string(dir.join("degree_distribution.csv")).unwrap();
+    assert_eq!(csv, "value,count\n1,2\n2,1\n");
+    let fit_json = fs::read_to_string(dir.join("power_law.json")).unwrap();
+    assert!(fit_json.contains("\"alpha\": 2.5"), "{}", fit_json);
+
+    let manifest = fs::read_to_string(&manifest_path).unwrap();
+    assert!(manifest.contains("\"schema_version\": 1"), "{}", manifest);
+    assert!(manifest.contains("degree_distribution.csv"), "{}", manifest);
+    assert!(manifest.contains("\"input\": \"toy.txt\""), "{}", manifest);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
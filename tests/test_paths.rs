@@ -0,0 +1,122 @@
+//! Tests for shortest-path statistics in `graph_analysis`.
+
+use ds210_project::graph_analysis::{
+    EstimateOptions, PathSampling, estimate_average_path, estimate_average_path_adaptive,
+    path_length_stats,
+};
+use petgraph::{Graph, Undirected};
+
+/// Build a path on `n` nodes: 0–1–…–(n−1).
+fn path_graph(n: usize) -> Graph<usize, (), Undirected> {
+    let mut g = Graph::new_undirected();
+    let nodes: Vec<_> = (0..n).map(|i| g.add_node(i)).collect();
+    for w in nodes.windows(2) {
+        g.add_edge(w[0], w[1], ());
+    }
+    g
+}
+
+/// Build an `r × c` grid graph; node payload = row * c + col.
+fn grid_graph(r: usize, c: usize) -> Graph<usize, (), Undirected> {
+    let mut g = Graph::new_undirected();
+    let nodes: Vec<_> = (0..r * c).map(|i| g.add_node(i)).collect();
+    for i in 0..r {
+        for j in 0..c {
+            if j + 1 < c {
+                g.add_edge(nodes[i * c + j], nodes[i * c + j + 1], ());
+            }
+            if i + 1 < r {
+                g.add_edge(nodes[i * c + j], nodes[(i + 1) * c + j], ());
+            }
+        }
+    }
+    g
+}
+
+#[test]
+/// On path 0–1–2–3 the 12 ordered pairs sum to 20 hops; diameter 3, radius 2.
+fn exact_path4() {
+    let ps = path_length_stats(&path_graph(4), PathSampling::Exact);
+    assert_eq!(ps.sources, 4);
+    assert_eq!(ps.reachable_pairs, 12);
+    assert!((ps.average_path_length - 20.0 / 12.0).abs() < 1e-12);
+    assert_eq!(ps.diameter, 3);
+    assert_eq!(ps.radius, 2);
+    assert_eq!(ps.eccentricities[&0], 3);
+    assert_eq!(ps.eccentricities[&1], 2);
+}
+
+#[test]
+/// Two disjoint edges: only within-component pairs count.
+fn exact_disconnected() {
+    let mut g = Graph::<usize, (), Undirected>::new_undirected();
+    let a = g.add_node(10);
+    let b = g.add_node(11);
+    let c = g.add_node(20);
+    let d = g.add_node(21);
+    g.add_edge(a, b, ());
+    g.add_edge(c, d, ());
+
+    let ps = path_length_stats(&g, PathSampling::Exact);
+    assert_eq!(ps.reachable_pairs, 4);
+    assert!((ps.average_path_length - 1.0).abs() < 1e-12);
+    assert_eq!(ps.diameter, 1);
+}
+
+#[test]
+/// Sampling is reproducible for a fixed seed and uses distinct sources.
+fn sampled_is_seeded() {
+    let g = path_graph(50);
+    let s1 = path_length_stats(&g, PathSampling::Sampled { sample_size: 10, seed: 3 });
+    let s2 = path_length_stats(&g, PathSampling::Sampled { sample_size: 10, seed: 3 });
+    assert_eq!(s1.sources, 10);
+    assert_eq!(s1.eccentricities, s2.eccentricities);
+    assert_eq!(s1.eccentricities.len(), 10);
+
+    // asking for more sources than nodes degrades to the exact answer
+    let all = path_length_stats(&g, PathSampling::Sampled { sample_size: 500, seed: 3 });
+    let exact = path_length_stats(&g, PathSampling::Exact);
+    assert!((all.average_path_length - exact.average_path_length).abs() < 1e-12);
+}
+
+#[test]
+/// Same seed → same estimate; the interval brackets the estimate and is
+/// reasonably close to the exact value on a 12×12 grid.
+fn estimate_seeded_with_interval() {
+    let g = grid_graph(12, 12);
+    let opts = EstimateOptions { sample_size: 30, seed: 9, ..EstimateOptions::default() };
+    let a = estimate_average_path(&g, &opts);
+    let b = estimate_average_path(&g, &opts);
+    assert_eq!(a.estimate, b.estimate);
+    assert_eq!(a.ci_low, b.ci_low);
+    assert_eq!(a.sources, 30);
+    assert!(a.standard_error > 0.0);
+    assert!(a.ci_low <= a.estimate && a.estimate <= a.ci_high);
+
+    let exact = path_length_stats(&g, PathSampling::Exact).average_path_length;
+    assert!((a.estimate - exact).abs() < 4.0 * a.standard_error, "{:?} vs {}", a, exact);
+}
+
+#[test]
+/// Sampling every node gives the exact value with zero uncertainty.
+fn estimate_full_sample_is_exact() {
+    let g = grid_graph(5, 5);
+    let opts = EstimateOptions { sample_size: 1000, ..EstimateOptions::default() };
+    let est = estimate_average_path(&g, &opts);
+    let exact = path_length_stats(&g, PathSampling::Exact).average_path_length;
+    assert!(est.exhausted);
+    assert!((est.estimate - exact).abs() < 1e-12);
+    assert_eq!(est.standard_error, 0.0);
+    assert_eq!(est.half_width(), 0.0);
+}
+
+#[test]
+/// Adaptive sampling stops once the half-width meets the tolerance.
+fn adaptive_meets_tolerance() {
+    let g = grid_graph(15, 15);
+    let opts = EstimateOptions { sample_size: 4, seed: 1, ..EstimateOptions::default() };
+    let est = estimate_average_path_adaptive(&g, 0.25, &opts);
+    assert!(est.half_width() <= 0.25 || est.exhausted, "{:?}", est);
+    assert!(est.sources > 4);
+    assert!(est.sources < 225, "should stop before exhausting: {:?}", est);
+}
@@ -1,18 +1,84 @@
-//! Tests for the discrete power-law MLE estimator in `stats`.
+//! Tests for the histogram summaries, power-law fits and distribution
+//! comparisons in `stats`.
 
-use ds210_project::stats::mle_power_law_exponent;
+use ds210_project::graph_analysis::two_hop_distribution;
+use ds210_project::stats::{
+    Alternative, GoodnessOfFitOptions, Histogram, LikelihoodRatio, PowerLawMethod, TailModel,
+    compare_to_power_law, fit_power_law, fit_power_law_tail, mle_power_law_exponent,
+    power_law_gof,
+};
+use petgraph::{Graph, Undirected};
+use rand::{Rng, SeedableRng, rngs::StdRng};
 use std::collections::HashMap;
 
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-12
+}
+
+#[test]
+/// Observations 1, 1, 2, 4: every statistic by hand.
+fn histogram_summary_by_hand() {
+    let hist = Histogram::new(&HashMap::from([(1, 2), (2, 1), (4, 1), (3, 0)])).unwrap();
+    let s = hist.summary();
+    assert_eq!((s.count, s.distinct, s.min, s.max, s.mode), (4, 3, 1, 4, 1));
+    assert!(close(s.mean, 2.0) && close(s.variance, 1.5));
+    assert!(close(s.skewness, 1.0 / 1.5f64.sqrt()));
+    assert!(close(s.excess_kurtosis, -1.0));
+    // type-7 quantiles: h = 3q
+    assert!(close(s.median, 1.5));
+    assert!(close(hist.quantile(0.25), 1.0));
+    assert!(close(hist.quantile(0.9), 3.4));
+    assert!(close(hist.quantile(1.0), 4.0));
+    // 2·(1 + 2 + 6 + 16)/(4·8) − 5/4
+    assert!(close(s.gini, 0.3125));
+    assert!(close(s.entropy_bits, 1.5));
+
+    let ccdf: Vec<(usize, f64)> = hist.ccdf().iter().map(|p| (p.value, p.ccdf)).collect();
+    assert_eq!(ccdf, vec![(1, 1.0), (2, 0.5), (4, 0.25)]);
+}
+
+#[test]
+/// Integer log bins: edges ⌈10^(i/10)⌉ give [1,2), [2,3), [3,4), [4,6), …;
+/// empty bins and the value 0 are skipped, densities divide by all n.
+fn histogram_log_bins() {
+    let hist = Histogram::new(&HashMap::from([(0, 4), (1, 2), (2, 1), (5, 1)])).unwrap();
+    let bins: Vec<(usize, usize, usize)> =
+        hist.log_bins(10).iter().map(|b| (b.lower, b.upper, b.count)).collect();
+    assert_eq!(bins, vec![(1, 2, 2), (2, 3, 1), (4, 6, 1)]);
+    assert!(close(hist.log_bins(10)[2].density, 1.0 / 16.0));
+
+    let decade = hist.log_bins(1);
+    assert_eq!((decade[0].lower, decade[0].upper, decade[0].count), (1, 10, 4));
+    let total: f64 = decade.iter().map(|b| b.density * (b.upper - b.lower) as f64).sum();
+    assert!(close(total, 0.5));
+}
+
 #[test]
-/// Synthetic degree counts ∝ k^(−3), k=1..10 → expect estimated exponent ≈ 3 within 1%.
+/// Constant and empty histograms.
+fn histogram_degenerate_cases() {
+    assert!(Histogram::new(&HashMap::new()).is_none());
+    assert!(Histogram::new(&HashMap::from([(3, 0)])).is_none());
+
+    let s = Histogram::new(&HashMap::from([(7, 5)])).unwrap().summary();
+    assert!(close(s.variance, 0.0) && close(s.skewness, 0.0) && close(s.excess_kurtosis, 0.0));
+    assert!(close(s.gini, 0.0) && close(s.entropy_bits, 0.0));
+    assert!(s.quantiles.iter().all(|p| close(p.value, 7.0)));
+
+    let zeros = Histogram::new(&HashMap::from([(0, 3)])).unwrap();
+    assert!(close(zeros.gini(), 0.0));
+    assert!(zeros.log_bins(5).is_empty());
+}
+
+#[test]
+/// Synthetic degree counts ∝ k^(−3), k=1..100 → expect estimated exponent ≈ 3 within 1%.
 fn mle_power_law_exponent_exact() {
     let alpha_true = 3.0;
     let k_min = 1;
     let mut counts = HashMap::new();
-    // generate counts = round(1000 * k^(-3))
-    for k in 1..=10 {
+    // generate counts = round(10⁶ * k^(-3)); coarser or shorter tables bias the MLE
+    for k in 1..=100 {
         let weight = (k as f64).powf(-alpha_true);
-        counts.insert(k, (weight * 1000.0).round() as usize);
+        counts.insert(k, (weight * 1e6).round() as usize);
     }
 
     let alpha_hat = mle_power_law_exponent(&counts, k_min);
@@ -25,3 +91,246 @@ fn mle_power_law_exponent_exact() {
         rel_err
     );
 }
+
+/// Histogram of `n` seeded draws from the discrete power law k^(−α)/ζ(α, k_min),
+/// by inverse-CDF lookup over k < 10⁵ (the truncated mass is negligible).
+fn sample_power_law(alpha: f64, k_min: usize, n: usize, seed: u64) -> HashMap<usize, usize> {
+    let mut cdf: Vec<f64> = Vec::new();
+    let mut total = 0.0;
+    for k in k_min..100_000 {
+        total += (k as f64).powf(-alpha);
+        cdf.push(total);
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut counts = HashMap::new();
+    for _ in 0..n {
+        let u = rng.random::<f64>() * total;
+        let i = cdf.partition_point(|&c| c < u);
+        *counts.entry(k_min + i).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[test]
+/// On seeded power-law samples the discrete MLE recovers α within 3 standard
+/// errors, and its standard error is close to the continuous (α̂ − 1)/√n.
+fn discrete_mle_recovers_exponent() {
+    for (alpha, k_min, seed) in [(2.5, 1, 1), (2.0, 1, 2), (3.0, 5, 3)] {
+        let counts = sample_power_law(alpha, k_min, 20_000, seed);
+        let fit = fit_power_law(&counts, k_min, PowerLawMethod::Discrete).unwrap();
+        assert_eq!(fit.tail_size, 20_000);
+        assert!(
+            (fit.alpha - alpha).abs() < 3.0 * fit.std_error,
+            "α = {}: α̂ = {} ± {}",
+            alpha,
+            fit.alpha,
+            fit.std_error
+        );
+        let continuous_se = (fit.alpha - 1.0) / (fit.tail_size as f64).sqrt();
+        assert!((fit.std_error / continuous_se - 1.0).abs() < 0.5, "{:?}", fit);
+    }
+}
+
+#[test]
+/// The continuous approximation is close to the discrete MLE once k_min ≳ 6,
+/// and both ignore degrees below k_min.
+fn continuous_approximation_and_tail() {
+    let mut counts = sample_power_law(2.5, 10, 5_000, 7);
+    counts.insert(3, 1_000); // below k_min, must not matter
+    let exact = fit_power_law(&counts, 10, PowerLawMethod::Discrete).unwrap();
+    let approx = fit_power_law(&counts, 10, PowerLawMethod::ContinuousApprox).unwrap();
+    assert_eq!(exact.tail_size, 5_000);
+    assert!((exact.alpha - approx.alpha).abs() < 0.01 * exact.alpha, "{:?} {:?}", exact, approx);
+    assert!(exact.log_likelihood >= approx.log_likelihood);
+}
+
+#[test]
+/// No fit without k_min ≥ 1, at least two tail observations and some spread.
+fn degenerate_tails_have_no_fit() {
+    let counts: HashMap<usize, usize> = [(1, 5), (2, 3), (7, 1)].into_iter().collect();
+    assert!(fit_power_law(&counts, 0, PowerLawMethod::Discrete).is_none());
+    assert!(fit_power_law(&counts, 7, PowerLawMethod::Discrete).is_none());
+    assert!(fit_power_law(&counts, 2, PowerLawMethod::Discrete).is_some());
+    let flat: HashMap<usize, usize> = [(1, 3), (4, 10)].into_iter().collect();
+    assert!(fit_power_law(&flat, 4, PowerLawMethod::ContinuousApprox).is_none());
+    assert_eq!(mle_power_law_exponent(&flat, 4), 0.0);
+}
+
+#[test]
+/// A power-law tail above 20 glued onto a flat bulk below it: the KS scan
+/// places k_min near the junction, recovers α, and no candidate beats it.
+fn ks_scan_finds_tail() {
+    let mut counts = sample_power_law(2.5, 20, 5_000, 11);
+    for k in 1..20 {
+        counts.insert(k, 400);
+    }
+    let best = fit_power_law_tail(&counts, PowerLawMethod::Discrete).unwrap();
+    assert!((15..=30).contains(&best.k_min), "{:?}", best);
+    assert!((best.alpha - 2.5).abs() < 3.0 * best.std_error, "{:?}", best);
+    for k_min in [1, 5, 10, 20, 40, 80] {
+        let fit = fit_power_law(&counts, k_min, PowerLawMethod::Discrete).unwrap();
+        assert!(best.ks_distance <= fit.ks_distance, "k_min {}: {:?}", k_min, fit);
+    }
+    let whole = fit_power_law(&counts, 1, PowerLawMethod::Discrete).unwrap();
+    assert!(whole.ks_distance > 0.1, "{:?}", whole);
+    assert!(best.ks_distance < 0.02, "{:?}", best);
+}
+
+#[test]
+/// Data drawn from the fitted family pass the bootstrap test; a geometric
+/// histogram fails it; the p-value depends only on the seed.
+fn bootstrap_goodness_of_fit() {
+    let opts = GoodnessOfFitOptions { replicates: 60, seed: 5, select_k_min: false };
+    let counts = sample_power_law(2.5, 2, 500, 13);
+    let fit = fit_power_law(&counts, 2, PowerLawMethod::Discrete).unwrap();
+    let gof = power_law_gof(&counts, &fit, &opts);
+    assert_eq!(gof.replicates, 60);
+    assert_eq!(gof.observed_ks, fit.ks_distance);
+    assert!(gof.p_value > 0.1, "{:?}", gof);
+    assert_eq!(gof, power_law_gof(&counts, &fit, &opts));
+
+    // counts ∝ 0.7^k: exponential, not power-law, decay
+    let geometric: HashMap<usize, usize> =
+        (1..30).map(|k| (k, (2000.0 * 0.7f64.powi(k as i32)).round() as usize)).collect();
+    let fit = fit_power_law(&geometric, 1, PowerLawMethod::Discrete).unwrap();
+    let gof = power_law_gof(&geometric, &fit, &opts);
+    assert_eq!(gof.p_value, 0.0, "{:?}", gof);
+}
+
+#[test]
+/// With k_min re-selected per replicate, the synthetic bulk below k_min is
+/// resampled from the data and a planted tail is still accepted.
+fn bootstrap_with_k_min_selection() {
+    let mut counts = sample_power_law(2.5, 10, 400, 17);
+    for k in 1..10 {
+        counts.insert(k, 30);
+    }
+    let fit = fit_power_law_tail(&counts, PowerLawMethod::Discrete).unwrap();
+    let opts = GoodnessOfFitOptions { replicates: 20, seed: 9, select_k_min: true };
+    let gof = power_law_gof(&counts, &fit, &opts);
+    assert_eq!(gof.replicates, 20);
+    assert!(gof.p_value > 0.1, "{:?} for {:?}", gof, fit);
+}
+
+/// Histogram of `n` seeded draws of `draw`, a real-valued sampler, rounded to
+/// the nearest integer (the binning the continuous alternatives assume).
+fn sample_rounded(n: usize, seed: u64, draw: impl Fn(&mut StdRng) -> f64) -> HashMap<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut counts = HashMap::new();
+    for _ in 0..n {
+        *counts.entry(draw(&mut rng).round() as usize).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let (u, v): (f64, f64) = (1.0 - rng.random::<f64>(), rng.random());
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
+
+/// Power-law fit at `k_min` and its comparison with `alternative`.
+fn compare(counts: &HashMap<usize, usize>, k_min: usize, alternative: Alternative) -> LikelihoodRatio {
+    let fit = fit_power_law(counts, k_min, PowerLawMethod::Discrete).unwrap();
+    compare_to_power_law(counts, &fit, alternative).unwrap()
+}
+
+#[test]
+/// Each alternative recovers the parameters of data drawn from it and beats
+/// the power law significantly on that data.
+fn alternatives_recover_their_parameters() {
+    let lognormal = sample_rounded(5_000, 21, |rng| (3.0 + 0.8 * standard_normal(rng)).exp());
+    let lr = compare(&lognormal, 1, Alternative::Lognormal);
+    match lr.model {
+        TailModel::Lognormal { mu, sigma } => {
+            assert!((mu - 3.0).abs() < 0.05 && (sigma - 0.8).abs() < 0.05, "{:?}", lr)
+        }
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(lr.favoured(0.01), Some("alternative"), "{:?}", lr);
+
+    let exponential = sample_rounded(5_000, 22, |rng| -(1.0 - rng.random::<f64>()).ln() / 0.1);
+    let lr = compare(&exponential, 1, Alternative::Exponential);
+    match lr.model {
+        TailModel::Exponential { lambda } => assert!((lambda - 0.1).abs() < 0.005, "{:?}", lr),
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(lr.favoured(0.01), Some("alternative"), "{:?}", lr);
+
+    let weibull = sample_rounded(5_000, 23, |rng| {
+        (-(1.0 - rng.random::<f64>()).ln() / 0.5).powf(1.0 / 0.4)
+    });
+    let lr = compare(&weibull, 1, Alternative::StretchedExponential);
+    match lr.model {
+        TailModel::StretchedExponential { lambda, beta } => {
+            assert!((lambda - 0.5).abs() < 0.05 && (beta - 0.4).abs() < 0.03, "{:?}", lr)
+        }
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(lr.favoured(0.01), Some("alternative"), "{:?}", lr);
+
+    // Poisson(30) by counting unit-rate arrivals, keeping the tail k ≥ 20
+    let poisson = sample_rounded(5_000, 24, |rng| {
+        let (mut k, mut t) = (0.0, -(1.0 - rng.random::<f64>()).ln());
+        while t < 30.0 {
+            k += 1.0;
+            t -= (1.0 - rng.random::<f64>()).ln();
+        }
+        k
+    });
+    let lr = compare(&poisson, 20, Alternative::Poisson);
+    match lr.model {
+        TailModel::Poisson { mu } => assert!((mu - 30.0).abs() < 0.5, "{:?}", lr),
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(lr.favoured(0.01), Some("alternative"), "{:?}", lr);
+
+    // k^(−1.5)·e^(−k/100) by rejection from the power law
+    let mut rng = StdRng::seed_from_u64(25);
+    let mut cutoff = HashMap::new();
+    let power_law = sample_power_law(1.5, 1, 40_000, 26);
+    for (&k, &cnt) in &power_law {
+        let kept = (0..cnt).filter(|_| rng.random::<f64>() < (-(k as f64) / 100.0).exp()).count();
+        if kept > 0 {
+            cutoff.insert(k, kept);
+        }
+    }
+    let lr = compare(&cutoff, 1, Alternative::PowerLawCutoff);
+    match lr.model {
+        TailModel::PowerLawCutoff { alpha, lambda } => {
+            assert!((alpha - 1.5).abs() < 0.05 && (lambda - 0.01).abs() < 0.002, "{:?}", lr)
+        }
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(lr.favoured(0.01), Some("alternative"), "{:?}", lr);
+}
+
+#[test]
+/// On power-law data the thin-tailed alternatives lose, the nested cutoff
+/// gains next to nothing, and every alternative runs on a 2-hop histogram.
+fn power_law_data_favour_the_power_law() {
+    let counts = sample_power_law(2.5, 5, 5_000, 27);
+    // a few huge draws dominate the variance of the pointwise ratios, so
+    // even the Poisson's enormous R is only significant at the 5% level
+    for alternative in [Alternative::Exponential, Alternative::Poisson] {
+        let lr = compare(&counts, 5, alternative);
+        assert_eq!(lr.favoured(0.05), Some("power law"), "{:?}", lr);
+    }
+    let fit = fit_power_law(&counts, 5, PowerLawMethod::Discrete).unwrap();
+    let lr = compare_to_power_law(&counts, &fit, Alternative::PowerLawCutoff).unwrap();
+    assert!(lr.log_likelihood >= fit.log_likelihood - 1e-6, "{:?}", lr);
+    assert!(lr.ratio < 1e-6 && lr.ratio > -3.0, "{:?}", lr);
+    assert_eq!(lr.favoured(0.01), None, "{:?}", lr);
+
+    let mut g = Graph::<usize, (), Undirected>::new_undirected();
+    let nodes: Vec<_> = (0..60).map(|i| g.add_node(i)).collect();
+    for i in 1..60 {
+        g.add_edge(nodes[i], nodes[(i * 7) % i], ());
+        g.add_edge(nodes[i], nodes[i - 1], ());
+    }
+    let two_hop = two_hop_distribution(&g);
+    let fit = fit_power_law(&two_hop, 1, PowerLawMethod::Discrete).unwrap();
+    for alternative in Alternative::ALL {
+        let lr = compare_to_power_law(&two_hop, &fit, alternative).unwrap();
+        assert!(lr.log_likelihood.is_finite() && lr.p_value.is_finite(), "{:?}", lr);
+    }
+}
@@ -0,0 +1,92 @@
+//! Tests for the k-truss decomposition in `graph_analysis`.
+
+mod common;
+
+use common::{k4_with_tail, lcg_graph};
+use ds210_project::graph_analysis::truss_decomposition;
+use petgraph::{Graph, Undirected};
+use std::collections::{HashMap, HashSet};
+
+/// Trussness from the definition: for each k, delete edges in fewer than
+/// k − 2 surviving triangles until none remain.
+fn naive_trussness(g: &Graph<usize, (), Undirected>) -> HashMap<(usize, usize), usize> {
+    let all: HashSet<(usize, usize)> = g
+        .edge_indices()
+        .map(|e| {
+            let (a, b) = g.edge_endpoints(e).unwrap();
+            (g[a].min(g[b]), g[a].max(g[b]))
+        })
+        .collect();
+    let mut truss: HashMap<_, _> = all.iter().map(|&e| (e, 2)).collect();
+    for k in 3..=g.node_count() {
+        let mut alive = all.clone();
+        loop {
+            let has = |a: usize, b: usize, alive: &HashSet<(usize, usize)>| {
+                alive.contains(&(a.min(b), a.max(b)))
+            };
+            let drop: Vec<_> = alive
+                .iter()
+                .copied()
+                .filter(|&(u, v)| {
+                    let support = g.node_weights().filter(|&&w| has(u, w, &alive) && has(v, w, &alive)).count();
+                    support + 2 < k
+                })
+                .collect();
+            if drop.is_empty() {
+                break;
+            }
+            for e in drop {
+                alive.remove(&e);
+            }
+        }
+        for e in alive {
+            truss.insert(e, k);
+        }
+    }
+    truss
+}
+
+#[test]
+/// K4 edges are in the 4-truss, tail edges only in the 2-truss.
+fn k4_with_tail_truss() {
+    let g = k4_with_tail();
+    let truss = truss_decomposition(&g);
+    assert_eq!(truss.max_truss, 4);
+    assert_eq!(truss.trussness.len(), 9);
+    assert_eq!(truss.trussness[&(0, 1)], 4);
+    assert_eq!(truss.trussness[&(3, 4)], 2);
+    assert_eq!(truss.k_truss_edges(4).len(), 6);
+
+    let max = truss.k_truss(&g, truss.max_truss);
+    assert_eq!((max.node_count(), max.edge_count()), (4, 6));
+    assert_eq!(truss.k_truss(&g, 2).node_count(), 7);
+
+    let dist = truss.truss_size_distribution();
+    assert_eq!((dist[&4], dist[&2]), (6, 3));
+}
+
+#[test]
+/// Parallel edges and self-loops do not change trussness.
+fn multigraph_is_simplified() {
+    let mut g = k4_with_tail();
+    let a = g.node_indices().next().unwrap();
+    let b = g.node_indices().nth(1).unwrap();
+    g.add_edge(a, b, ());
+    g.add_edge(a, a, ());
+    let truss = truss_decomposition(&g);
+    assert_eq!(truss.trussness.len(), 9);
+    assert_eq!(truss.max_truss, 4);
+    let max = truss.k_truss(&g, 4);
+    assert_eq!(max.node_count(), 4);
+}
+
+#[test]
+/// The peeling decomposition agrees with the definition on random graphs.
+fn trussness_matches_definition() {
+    for seed in 0..6 {
+        let g = lcg_graph(12, 34, seed);
+        let truss = truss_decomposition(&g);
+        assert_eq!(truss.trussness, naive_trussness(&g), "seed {}", seed);
+        assert_eq!(truss.max_truss, *truss.trussness.values().max().unwrap());
+    }
+}